@@ -1,57 +1,219 @@
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+CREATE EXTENSION IF NOT EXISTS fuzzystrmatch;
+
 CREATE TABLE users (
-    id SERIAL PRIMARY KEY,
+    id BIGSERIAL PRIMARY KEY,
+    public_id UUID UNIQUE NOT NULL,
     username VARCHAR(100) UNIQUE NOT NULL,
     email VARCHAR(255) UNIQUE,
     password_hash VARCHAR(255) NOT NULL,
-    salt VARCHAR(32) NOT NULL
+    salt VARCHAR(32) NOT NULL,
+    role VARCHAR(20) NOT NULL DEFAULT 'user'
+        CHECK (role IN ('user', 'editor', 'moderator', 'admin'))
 );
 
 CREATE INDEX CONCURRENTLY idx_users_username ON users (username);
 CREATE INDEX CONCURRENTLY idx_users_email ON users (email);
 
 CREATE TABLE sessions (
-    id SERIAL PRIMARY KEY,
+    id BIGSERIAL PRIMARY KEY,
     session_id VARCHAR(255) UNIQUE NOT NULL,
-    user_id INT NOT NULL,
+    user_id BIGINT NOT NULL,
     created_at TIMESTAMP DEFAULT NOW()
 );
 
 CREATE INDEX CONCURRENTLY idx_sessions_session_id ON sessions (session_id);
 CREATE INDEX CONCURRENTLY idx_sessions_user_id ON sessions (user_id);
 
+CREATE TABLE media (
+    id BIGSERIAL PRIMARY KEY,
+    public_id UUID UNIQUE NOT NULL,
+    owner_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    storage_key VARCHAR(255) NOT NULL,
+    thumbnail_key VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX CONCURRENTLY idx_media_owner_id ON media (owner_id);
+CREATE INDEX CONCURRENTLY idx_media_created_at ON media (created_at);
+
+CREATE TABLE sections (
+    id BIGSERIAL PRIMARY KEY,
+    public_id UUID UNIQUE NOT NULL,
+    parent_id BIGINT REFERENCES sections(id),
+    name VARCHAR(100) NOT NULL,
+    slug VARCHAR(100) NOT NULL,
+    -- Slugs from the top-level section down joined with `/`, e.g. `world/europe`.
+    path TEXT UNIQUE NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX CONCURRENTLY idx_sections_parent_id ON sections (parent_id);
+CREATE INDEX CONCURRENTLY idx_sections_path_pattern ON sections (path text_pattern_ops);
+
+-- Articles are filed here unless another section is picked.
+INSERT INTO sections (public_id, name, slug, path) VALUES (gen_random_uuid(), 'General', 'general', 'general');
+
 CREATE TABLE articles (
-    id SERIAL PRIMARY KEY,
-    author_id INTEGER NOT NULL REFERENCES users(id),
+    id BIGSERIAL PRIMARY KEY,
+    public_id UUID UNIQUE NOT NULL,
+    author_id BIGINT NOT NULL REFERENCES users(id),
     title VARCHAR(255) NOT NULL,
+    slug VARCHAR(100) UNIQUE NOT NULL,
     content TEXT NOT NULL,
-    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+    content_format VARCHAR(20) NOT NULL DEFAULT 'plain'
+        CHECK (content_format IN ('plain', 'markdown', 'html')),
+    content_html TEXT NOT NULL DEFAULT '',
+    excerpt TEXT NOT NULL DEFAULT '',
+    reading_time_minutes INTEGER NOT NULL DEFAULT 0,
+    status VARCHAR(20) NOT NULL DEFAULT 'draft'
+        CHECK (status IN ('draft', 'scheduled', 'published', 'archived')),
+    publish_at TIMESTAMP,
+    version INTEGER NOT NULL DEFAULT 1,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- Last change of anything but the view count, set by articles_touched.
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    deleted_at TIMESTAMP,
+    deleted_by BIGINT REFERENCES users(id),
+    cover_media_id BIGINT REFERENCES media(id) ON DELETE SET NULL,
+    view_count BIGINT NOT NULL DEFAULT 0,
+    section_id BIGINT NOT NULL REFERENCES sections(id),
+    -- ISO 639-1 (or 639-2) code, e.g. de.
+    language VARCHAR(3) NOT NULL DEFAULT 'en' CHECK (language ~ '^[a-z]{2,3}$'),
+    -- Text search configuration stemming the language, simple for languages without one.
+    search_config REGCONFIG NOT NULL DEFAULT 'english',
+    search_vector TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector(search_config, title), 'A')
+            || setweight(to_tsvector(search_config, content), 'B')
+    ) STORED,
+    -- Translations of one another share the group, one article per language.
+    translation_group_id UUID NOT NULL,
+    -- Hidden by a moderator or held by the content filter, shown to moderators, the author and
+    -- contributors only.
+    hidden_at TIMESTAMP,
+    -- Whitespace- and case-insensitive, for the content filter to spot duplicates.
+    content_hash TEXT GENERATED ALWAYS AS (
+        md5(lower(btrim(regexp_replace(content, '\s+', ' ', 'g'))))
+    ) STORED
 );
 
 CREATE INDEX CONCURRENTLY idx_articles_author_id ON articles (author_id);
 CREATE INDEX CONCURRENTLY idx_articles_created_at ON articles (created_at);
-CREATE INDEX CONCURRENTLY idx_articles_title ON articles USING gin (to_tsvector('english', title));
-CREATE INDEX CONCURRENTLY idx_articles_content ON articles USING gin (to_tsvector('english', content));
+CREATE INDEX CONCURRENTLY idx_articles_updated_at ON articles (updated_at);
+CREATE INDEX CONCURRENTLY idx_articles_status_publish_at ON articles (status, publish_at, id);
+CREATE INDEX CONCURRENTLY idx_articles_deleted_at ON articles (deleted_at);
+CREATE INDEX CONCURRENTLY idx_articles_cover_media_id ON articles (cover_media_id);
+CREATE INDEX CONCURRENTLY idx_articles_section_id ON articles (section_id);
+CREATE INDEX CONCURRENTLY idx_articles_search_vector ON articles USING gin (search_vector);
+CREATE INDEX CONCURRENTLY idx_articles_title_trgm ON articles USING gin (title gin_trgm_ops);
+CREATE UNIQUE INDEX CONCURRENTLY idx_articles_translation_group_id_language
+    ON articles (translation_group_id, language) WHERE deleted_at IS NULL;
+CREATE INDEX CONCURRENTLY idx_articles_content_hash ON articles (content_hash);
+
+CREATE FUNCTION touch_article() RETURNS trigger AS $$
+BEGIN
+    NEW.updated_at := CURRENT_TIMESTAMP;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Counting views is no change readers of the article would notice.
+CREATE TRIGGER articles_touched
+    BEFORE UPDATE ON articles
+    FOR EACH ROW
+    WHEN (OLD.view_count = NEW.view_count)
+    EXECUTE FUNCTION touch_article();
+
+CREATE TABLE article_slugs (
+    slug VARCHAR(100) PRIMARY KEY,
+    article_id BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX CONCURRENTLY idx_article_slugs_article_id ON article_slugs (article_id);
+
+CREATE TABLE article_revisions (
+    id BIGSERIAL PRIMARY KEY,
+    article_id BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    editor_id BIGINT NOT NULL REFERENCES users(id),
+    title VARCHAR(255) NOT NULL,
+    content TEXT NOT NULL,
+    content_format VARCHAR(20) NOT NULL DEFAULT 'plain',
+    tags TEXT[] NOT NULL,
+    -- Media referenced inline, kept from being collected while a revision can be restored.
+    media_ids UUID[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (article_id, revision)
+);
+
+CREATE INDEX CONCURRENTLY idx_article_revisions_article_id ON article_revisions (article_id);
+CREATE INDEX CONCURRENTLY idx_article_revisions_media_ids ON article_revisions USING gin (media_ids);
+
+CREATE FUNCTION forbid_article_revision_update() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'article revisions are immutable';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER article_revisions_immutable
+    BEFORE UPDATE ON article_revisions
+    FOR EACH ROW EXECUTE FUNCTION forbid_article_revision_update();
+
+CREATE TABLE article_contributors (
+    article_id BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(20) NOT NULL
+        CHECK (role IN ('author', 'co_author', 'editor', 'photographer')),
+    position INTEGER NOT NULL,
+    invited_by BIGINT REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- Invitations stay pending until accepted, only accepted contributors are on the byline.
+    accepted_at TIMESTAMP,
+    PRIMARY KEY (article_id, user_id)
+);
+
+CREATE INDEX CONCURRENTLY idx_article_contributors_user_id ON article_contributors (user_id);
 
 CREATE TABLE tags (
-    id SERIAL PRIMARY KEY,
-    name VARCHAR(50) UNIQUE NOT NULL
+    id BIGSERIAL PRIMARY KEY,
+    name VARCHAR(50) UNIQUE NOT NULL,
+    -- Published articles using the tag, refreshed with the article scores.
+    article_count BIGINT NOT NULL DEFAULT 0
 );
 
 CREATE INDEX CONCURRENTLY idx_tags_name ON tags (name);
+-- Most used first, the order ListTags pages in.
+CREATE INDEX CONCURRENTLY idx_tags_article_count ON tags ((-article_count), name)
+    WHERE article_count > 0;
+CREATE INDEX CONCURRENTLY idx_tags_name_trgm ON tags USING gin (name gin_trgm_ops);
 
 CREATE TABLE articles_tags (
-    article_id INTEGER NOT NULL REFERENCES articles(id),
-    tag_id INTEGER NOT NULL REFERENCES tags(id),
+    article_id BIGINT NOT NULL REFERENCES articles(id),
+    tag_id BIGINT NOT NULL REFERENCES tags(id),
     PRIMARY KEY (article_id, tag_id)
 );
 
 CREATE INDEX CONCURRENTLY idx_articles_tags_article_id ON articles_tags (article_id);
 CREATE INDEX CONCURRENTLY idx_articles_tags_tag_id ON articles_tags (tag_id);
 
+CREATE TABLE articles_media (
+    article_id BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    media_id BIGINT NOT NULL REFERENCES media(id) ON DELETE CASCADE,
+    PRIMARY KEY (article_id, media_id)
+);
+
+CREATE INDEX CONCURRENTLY idx_articles_media_media_id ON articles_media (media_id);
+
 CREATE TABLE likes (
-    id SERIAL PRIMARY KEY,
-    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
-    article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    article_id BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
     created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
     UNIQUE (user_id, article_id)
 );
@@ -59,15 +221,108 @@ CREATE TABLE likes (
 CREATE INDEX CONCURRENTLY idx_likes_user_id ON likes (user_id);
 CREATE INDEX CONCURRENTLY idx_likes_article_id ON likes (article_id);
 
+CREATE TABLE reading_lists (
+    id BIGSERIAL PRIMARY KEY,
+    public_id UUID UNIQUE NOT NULL,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    position INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (user_id, name)
+);
+
+CREATE INDEX CONCURRENTLY idx_reading_lists_user_id_position ON reading_lists (user_id, position);
+
+CREATE TABLE bookmarks (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    article_id BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    reading_list_id BIGINT REFERENCES reading_lists(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (user_id, article_id)
+);
+
+CREATE INDEX CONCURRENTLY idx_bookmarks_user_id_created_at ON bookmarks (user_id, created_at, id);
+CREATE INDEX CONCURRENTLY idx_bookmarks_article_id ON bookmarks (article_id);
+CREATE INDEX CONCURRENTLY idx_bookmarks_reading_list_id ON bookmarks (reading_list_id);
+
 CREATE TABLE comments (
-    id SERIAL PRIMARY KEY,
-    article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
-    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE SET NULL,
-    parent_id INTEGER REFERENCES comments(id) ON DELETE CASCADE,
+    id BIGSERIAL PRIMARY KEY,
+    public_id UUID UNIQUE NOT NULL DEFAULT gen_random_uuid(),
+    article_id BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE SET NULL,
+    parent_id BIGINT REFERENCES comments(id) ON DELETE CASCADE,
     content TEXT NOT NULL,
-    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- Set by moderators, such comments are left out like deleted ones.
+    hidden_at TIMESTAMP,
+    deleted_at TIMESTAMP
 );
 
 CREATE INDEX CONCURRENTLY idx_comments_article_id ON comments (article_id);
 CREATE INDEX CONCURRENTLY idx_comments_parent_id ON comments (parent_id);
-CREATE INDEX CONCURRENTLY idx_comments_created_at ON comments (created_at);
\ No newline at end of file
+CREATE INDEX CONCURRENTLY idx_comments_created_at ON comments (created_at);
+
+CREATE TABLE moderation_actions (
+    id BIGSERIAL PRIMARY KEY,
+    moderator_id BIGINT REFERENCES users(id) ON DELETE SET NULL,
+    action VARCHAR(20) NOT NULL
+        CHECK (action IN ('dismiss', 'hide', 'delete', 'warn', 'approve')),
+    article_id BIGINT REFERENCES articles(id) ON DELETE SET NULL,
+    comment_id BIGINT REFERENCES comments(id) ON DELETE SET NULL,
+    -- Author of the moderated content, the one warned by a warn action.
+    target_user_id BIGINT REFERENCES users(id) ON DELETE SET NULL,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX CONCURRENTLY idx_moderation_actions_target_user_id ON moderation_actions (target_user_id);
+
+CREATE TABLE reports (
+    id BIGSERIAL PRIMARY KEY,
+    public_id UUID UNIQUE NOT NULL,
+    -- NULL for holds of the content filter and once the reporter is gone.
+    reporter_id BIGINT REFERENCES users(id) ON DELETE SET NULL,
+    article_id BIGINT REFERENCES articles(id) ON DELETE CASCADE,
+    comment_id BIGINT REFERENCES comments(id) ON DELETE CASCADE,
+    reason VARCHAR(20) NOT NULL
+        CHECK (reason IN ('spam', 'abuse', 'misinformation', 'other')),
+    details VARCHAR(1000) NOT NULL DEFAULT '',
+    -- Content filter rule that held the content, NULL for reports of users.
+    held_by VARCHAR(100),
+    status VARCHAR(20) NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'resolved', 'dismissed')),
+    moderation_action_id BIGINT REFERENCES moderation_actions(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((article_id IS NULL) <> (comment_id IS NULL))
+);
+
+CREATE INDEX CONCURRENTLY idx_reports_status_created_at ON reports (status, created_at, id);
+CREATE INDEX CONCURRENTLY idx_reports_article_id ON reports (article_id);
+CREATE INDEX CONCURRENTLY idx_reports_comment_id ON reports (comment_id);
+-- One open report per reporter and reported article or comment.
+CREATE UNIQUE INDEX CONCURRENTLY idx_reports_open_article
+    ON reports (reporter_id, article_id) WHERE status = 'open' AND article_id IS NOT NULL;
+CREATE UNIQUE INDEX CONCURRENTLY idx_reports_open_comment
+    ON reports (reporter_id, comment_id) WHERE status = 'open' AND comment_id IS NOT NULL;
+
+CREATE TABLE article_scores (
+    article_id BIGINT PRIMARY KEY REFERENCES articles(id) ON DELETE CASCADE,
+    like_count BIGINT NOT NULL DEFAULT 0,
+    comment_count BIGINT NOT NULL DEFAULT 0,
+    trending_score DOUBLE PRECISION NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX CONCURRENTLY idx_article_scores_like_count ON article_scores (like_count, article_id);
+CREATE INDEX CONCURRENTLY idx_article_scores_trending_score ON article_scores (trending_score, article_id);
+
+CREATE TABLE article_view_buckets (
+    article_id BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    bucket_start TIMESTAMP NOT NULL,
+    view_count BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (article_id, bucket_start)
+);
+
+CREATE INDEX CONCURRENTLY idx_likes_article_id_created_at ON likes (article_id, created_at);
+CREATE INDEX CONCURRENTLY idx_comments_article_id_created_at ON comments (article_id, created_at);
\ No newline at end of file
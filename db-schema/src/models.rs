@@ -1,35 +1,487 @@
 use chrono::NaiveDateTime;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::pg::{Pg, PgValue};
 use diesel::prelude::*;
-use diesel::sql_types::{Array, Integer, Nullable, Text, Timestamp};
+use diesel::sql_types::{
+    Array, BigInt, Bool, Double, Integer, Jsonb, Nullable, Text, Timestamp, Uuid as SqlUuid,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// An accepted contributor as listed in an article byline.
+#[derive(Deserialize, Debug)]
+pub struct BylineEntry {
+    pub user_id: Uuid,
+    pub role: String,
+    pub username: String,
+}
+
+/// Accepted contributors in byline order, loaded as a JSON array.
+#[derive(FromSqlRow, Default, Debug)]
+#[diesel(sql_type = Jsonb)]
+pub struct Byline(pub Vec<BylineEntry>);
+
+impl FromSql<Jsonb, Pg> for Byline {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <serde_json::Value as FromSql<Jsonb, Pg>>::from_sql(value)?;
+        Ok(Byline(serde_json::from_value(value)?))
+    }
+}
 
 #[derive(Queryable, QueryableByName, Debug)]
 #[diesel(table_name = articles)]
 pub struct ArticleEntry {
-    #[diesel(sql_type = Integer)]
-    pub id: i32,
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+    #[diesel(sql_type = SqlUuid)]
+    pub public_id: Uuid,
+    #[diesel(sql_type = BigInt)]
+    pub author_id: i64,
     #[diesel(sql_type = Text)]
     pub title: String,
     #[diesel(sql_type = Text)]
+    pub slug: String,
+    #[diesel(sql_type = Text)]
     pub content: String,
+    #[diesel(sql_type = Text)]
+    pub content_format: String,
+    #[diesel(sql_type = Text)]
+    pub content_html: String,
+    #[diesel(sql_type = Text)]
+    pub excerpt: String,
+    #[diesel(sql_type = Integer)]
+    pub reading_time_minutes: i32,
+    #[diesel(sql_type = Text)]
+    pub status: String,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub publish_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Integer)]
+    pub version: i32,
     #[diesel(sql_type = Timestamp)]
     pub created_at: NaiveDateTime,
+    /// Last change of the article, its view count aside.
+    #[diesel(sql_type = Timestamp)]
+    pub updated_at: NaiveDateTime,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub deleted_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = BigInt)]
+    pub view_count: i64,
+    #[diesel(sql_type = Text)]
+    pub language: String,
+    #[diesel(sql_type = SqlUuid)]
+    pub translation_group_id: Uuid,
+    #[diesel(sql_type = Bool)]
+    pub hidden: bool,
+    #[diesel(sql_type = Bool)]
+    pub bookmarked_by_me: bool,
     #[diesel(sql_type = Array<Text>)]
     pub tags: Vec<String>,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    pub cover_media_id: Option<Uuid>,
+    #[diesel(sql_type = Array<SqlUuid>)]
+    pub media_ids: Vec<Uuid>,
+    #[diesel(sql_type = SqlUuid)]
+    pub author_public_id: Uuid,
     #[diesel(sql_type = Text)]
     pub author_username: String,
+    #[diesel(sql_type = SqlUuid)]
+    pub section_public_id: Uuid,
+    #[diesel(sql_type = Text)]
+    pub section_path: String,
+    #[diesel(sql_type = Jsonb)]
+    pub byline: Byline,
+}
+
+/// [`ArticleEntry`] without the content, loaded by list views.
+#[derive(QueryableByName, Debug)]
+pub struct ArticleSummaryEntry {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+    #[diesel(sql_type = SqlUuid)]
+    pub public_id: Uuid,
+    #[diesel(sql_type = Text)]
+    pub title: String,
+    #[diesel(sql_type = Text)]
+    pub slug: String,
+    #[diesel(sql_type = Text)]
+    pub content_format: String,
+    #[diesel(sql_type = Text)]
+    pub excerpt: String,
+    #[diesel(sql_type = Integer)]
+    pub reading_time_minutes: i32,
+    #[diesel(sql_type = Text)]
+    pub status: String,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub publish_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = Integer)]
+    pub version: i32,
+    #[diesel(sql_type = Timestamp)]
+    pub created_at: NaiveDateTime,
+    /// Last change of the article, its view count aside.
+    #[diesel(sql_type = Timestamp)]
+    pub updated_at: NaiveDateTime,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub deleted_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = BigInt)]
+    pub view_count: i64,
+    #[diesel(sql_type = Text)]
+    pub language: String,
+    #[diesel(sql_type = SqlUuid)]
+    pub translation_group_id: Uuid,
+    #[diesel(sql_type = Bool)]
+    pub hidden: bool,
+    #[diesel(sql_type = Bool)]
+    pub bookmarked_by_me: bool,
+    #[diesel(sql_type = Array<Text>)]
+    pub tags: Vec<String>,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    pub cover_media_id: Option<Uuid>,
+    #[diesel(sql_type = SqlUuid)]
+    pub author_public_id: Uuid,
+    #[diesel(sql_type = Text)]
+    pub author_username: String,
+    #[diesel(sql_type = SqlUuid)]
+    pub section_public_id: Uuid,
+    #[diesel(sql_type = Text)]
+    pub section_path: String,
+    #[diesel(sql_type = Jsonb)]
+    pub byline: Byline,
+}
+
+/// Content of an article missing from [`ArticleSummaryEntry`].
+#[derive(QueryableByName, Debug)]
+pub struct ArticleBodyEntry {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+    #[diesel(sql_type = Text)]
+    pub content: String,
+    #[diesel(sql_type = Text)]
+    pub content_html: String,
+    #[diesel(sql_type = Array<SqlUuid>)]
+    pub media_ids: Vec<Uuid>,
+}
+
+/// Article of a feed page with the scores it can be ranked by.
+#[derive(QueryableByName, Debug)]
+pub struct ArticlePageEntry {
+    #[diesel(embed)]
+    pub article: ArticleSummaryEntry,
+    #[diesel(sql_type = BigInt)]
+    pub like_count: i64,
+    #[diesel(sql_type = Double)]
+    pub trending_score: f64,
+}
+
+/// Bookmark of the caller, the reading list is empty for plain bookmarks.
+#[derive(QueryableByName, Debug)]
+pub struct BookmarkEntry {
+    #[diesel(sql_type = SqlUuid)]
+    pub article_public_id: Uuid,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    pub reading_list_public_id: Option<Uuid>,
+    #[diesel(sql_type = Timestamp)]
+    pub bookmarked_at: NaiveDateTime,
+}
+
+/// Bookmarked article of a bookmarks page, keyed by `(bookmarked_at, bookmark_id)`.
+#[derive(QueryableByName, Debug)]
+pub struct BookmarkPageEntry {
+    #[diesel(embed)]
+    pub article: ArticleSummaryEntry,
+    #[diesel(embed)]
+    pub bookmark: BookmarkEntry,
+    #[diesel(sql_type = BigInt)]
+    pub bookmark_id: i64,
+}
+
+#[derive(QueryableByName, Debug)]
+pub struct ReadingListEntry {
+    #[diesel(sql_type = SqlUuid)]
+    pub public_id: Uuid,
+    #[diesel(sql_type = Text)]
+    pub name: String,
+    #[diesel(sql_type = BigInt)]
+    pub bookmark_count: i64,
+    #[diesel(sql_type = Timestamp)]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(QueryableByName)]
+pub struct ReadingListId {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+}
+
+#[derive(QueryableByName, Debug)]
+pub struct SectionEntry {
+    #[diesel(sql_type = SqlUuid)]
+    pub public_id: Uuid,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    pub parent_public_id: Option<Uuid>,
+    #[diesel(sql_type = Text)]
+    pub name: String,
+    #[diesel(sql_type = Text)]
+    pub slug: String,
+    #[diesel(sql_type = Text)]
+    pub path: String,
+    #[diesel(sql_type = BigInt)]
+    pub article_count: i64,
+}
+
+/// Internal key of a section with what its subtree paths are derived from.
+#[derive(QueryableByName)]
+pub struct SectionKeyEntry {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+    #[diesel(sql_type = Text)]
+    pub slug: String,
+    #[diesel(sql_type = Text)]
+    pub path: String,
+}
+
+#[derive(QueryableByName)]
+pub struct SectionId {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
 }
 
 #[derive(QueryableByName)]
 pub struct ArticleId {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+    #[diesel(sql_type = SqlUuid)]
+    pub public_id: Uuid,
+}
+
+#[derive(QueryableByName)]
+pub struct ArticleSlugEntry {
+    #[diesel(sql_type = BigInt)]
+    pub article_id: i64,
+    #[diesel(sql_type = Text)]
+    pub slug: String,
+}
+
+#[derive(QueryableByName)]
+pub struct ArticlePublicationEntry {
+    #[diesel(sql_type = Text)]
+    pub status: String,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub publish_at: Option<NaiveDateTime>,
+    /// Whether the article has tags, ranking it among related articles.
+    #[diesel(sql_type = Bool)]
+    pub tagged: bool,
+}
+
+#[derive(QueryableByName)]
+pub struct LastModifiedEntry {
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub last_modified: Option<NaiveDateTime>,
+}
+
+#[derive(QueryableByName)]
+pub struct ScheduledPublicationEntry {
+    /// Whether the published article has tags, ranking it among related articles.
+    #[diesel(sql_type = Bool)]
+    pub tagged: bool,
+}
+
+#[derive(QueryableByName, Debug)]
+pub struct ArticleRevisionEntry {
+    #[diesel(sql_type = BigInt)]
+    pub article_id: i64,
+    #[diesel(sql_type = SqlUuid)]
+    pub article_public_id: Uuid,
     #[diesel(sql_type = Integer)]
-    pub id: i32,
+    pub revision: i32,
+    #[diesel(sql_type = Text)]
+    pub editor_username: String,
+    #[diesel(sql_type = Text)]
+    pub title: String,
+    #[diesel(sql_type = Text)]
+    pub content: String,
+    #[diesel(sql_type = Text)]
+    pub content_format: String,
+    #[diesel(sql_type = Array<Text>)]
+    pub tags: Vec<String>,
+    #[diesel(sql_type = Timestamp)]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(QueryableByName)]
+pub struct ArticleOwnershipEntry {
+    #[diesel(sql_type = BigInt)]
+    pub author_id: i64,
+    #[diesel(sql_type = Integer)]
+    pub version: i32,
+}
+
+/// [`ArticleOwnershipEntry`] with whether the user may edit the article as a contributor.
+#[derive(QueryableByName)]
+pub struct ArticleAccessEntry {
+    #[diesel(sql_type = BigInt)]
+    pub author_id: i64,
+    #[diesel(sql_type = Integer)]
+    pub version: i32,
+    #[diesel(sql_type = Bool)]
+    pub is_editor: bool,
+    #[diesel(sql_type = SqlUuid)]
+    pub translation_group_id: Uuid,
+}
+
+#[derive(QueryableByName, Debug)]
+pub struct ContributorEntry {
+    #[diesel(sql_type = SqlUuid)]
+    pub user_public_id: Uuid,
+    #[diesel(sql_type = Text)]
+    pub username: String,
+    #[diesel(sql_type = Text)]
+    pub role: String,
+    #[diesel(sql_type = Timestamp)]
+    pub created_at: NaiveDateTime,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub accepted_at: Option<NaiveDateTime>,
+}
+
+/// Report of an article or comment with the moderator action that closed it.
+#[derive(QueryableByName, Debug)]
+pub struct ReportEntry {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+    #[diesel(sql_type = SqlUuid)]
+    pub public_id: Uuid,
+    /// The reported article or the article of the reported comment.
+    #[diesel(sql_type = SqlUuid)]
+    pub article_public_id: Uuid,
+    #[diesel(sql_type = Text)]
+    pub article_title: String,
+    #[diesel(sql_type = Nullable<SqlUuid>)]
+    pub comment_public_id: Option<Uuid>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub comment_content: Option<String>,
+    #[diesel(sql_type = Text)]
+    pub reason: String,
+    #[diesel(sql_type = Text)]
+    pub details: String,
+    /// Content filter rule that held the content, `None` for reports of users.
+    #[diesel(sql_type = Nullable<Text>)]
+    pub held_by: Option<String>,
+    #[diesel(sql_type = Text)]
+    pub status: String,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub reporter_username: Option<String>,
+    #[diesel(sql_type = Timestamp)]
+    pub created_at: NaiveDateTime,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub action: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub moderator_username: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub moderation_reason: Option<String>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub moderated_at: Option<NaiveDateTime>,
+}
+
+/// What a report is about, loaded to act on it.
+#[derive(QueryableByName)]
+pub struct ReportTargetEntry {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+    #[diesel(sql_type = Nullable<BigInt>)]
+    pub article_id: Option<i64>,
+    #[diesel(sql_type = Nullable<BigInt>)]
+    pub comment_id: Option<i64>,
+    #[diesel(sql_type = Text)]
+    pub status: String,
+}
+
+#[derive(QueryableByName)]
+pub struct CommentKeyEntry {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+    #[diesel(sql_type = BigInt)]
+    pub article_id: i64,
+}
+
+#[derive(QueryableByName)]
+pub struct CountEntry {
+    #[diesel(sql_type = BigInt)]
+    pub count: i64,
+}
+
+#[derive(QueryableByName)]
+pub struct ReportId {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+}
+
+/// Pending invitation of the user to contribute to an article.
+#[derive(QueryableByName, Debug)]
+pub struct ContributorInvitationEntry {
+    #[diesel(sql_type = SqlUuid)]
+    pub article_public_id: Uuid,
+    #[diesel(sql_type = Text)]
+    pub article_title: String,
+    #[diesel(sql_type = Text)]
+    pub role: String,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub invited_by_username: Option<String>,
+    #[diesel(sql_type = Timestamp)]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(QueryableByName)]
+pub struct ArticleContentEntry {
+    #[diesel(sql_type = Text)]
+    pub content: String,
+    #[diesel(sql_type = Text)]
+    pub content_format: String,
+}
+
+#[derive(QueryableByName, Debug)]
+pub struct MediaEntry {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+    #[diesel(sql_type = SqlUuid)]
+    pub public_id: Uuid,
+    #[diesel(sql_type = Text)]
+    pub file_name: String,
+    #[diesel(sql_type = Text)]
+    pub content_type: String,
+    #[diesel(sql_type = BigInt)]
+    pub size_bytes: i64,
+    #[diesel(sql_type = Integer)]
+    pub width: i32,
+    #[diesel(sql_type = Integer)]
+    pub height: i32,
+    #[diesel(sql_type = Text)]
+    pub storage_key: String,
+    #[diesel(sql_type = Text)]
+    pub thumbnail_key: String,
+    #[diesel(sql_type = Timestamp)]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(QueryableByName)]
+pub struct MediaKeysEntry {
+    #[diesel(sql_type = Text)]
+    pub storage_key: String,
+    #[diesel(sql_type = Text)]
+    pub thumbnail_key: String,
+}
+
+#[derive(QueryableByName)]
+pub struct MediaId {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
 }
 
 #[derive(Queryable, QueryableByName, Debug)]
 #[diesel(table_name = users)]
 pub struct UserEntry {
-    #[diesel(sql_type = Integer)]
-    pub id: i32,
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+    #[diesel(sql_type = SqlUuid)]
+    pub public_id: Uuid,
     #[diesel(sql_type = Text)]
     pub username: String,
     #[diesel(sql_type = Nullable<Text>)]
@@ -42,6 +494,48 @@ pub struct UserEntry {
 
 #[derive(QueryableByName, Clone)]
 pub struct UserIdEntry {
-    #[diesel(sql_type = Integer)]
-    pub id: i32,
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+}
+
+#[derive(QueryableByName)]
+pub struct UserRoleEntry {
+    #[diesel(sql_type = Text)]
+    pub role: String,
+}
+
+#[derive(QueryableByName, Debug)]
+pub struct TagEntry {
+    #[diesel(sql_type = Text)]
+    pub name: String,
+    #[diesel(sql_type = BigInt)]
+    pub article_count: i64,
+}
+
+#[derive(QueryableByName)]
+pub struct TagId {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+}
+
+#[derive(QueryableByName)]
+pub struct ArticleStatsTotalsEntry {
+    #[diesel(sql_type = BigInt)]
+    pub view_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub like_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub comment_count: i64,
+}
+
+#[derive(QueryableByName)]
+pub struct ArticleStatsBucketEntry {
+    #[diesel(sql_type = Timestamp)]
+    pub bucket_start: NaiveDateTime,
+    #[diesel(sql_type = BigInt)]
+    pub view_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub like_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub comment_count: i64,
 }
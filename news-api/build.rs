@@ -19,6 +19,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .build_server(true)
         .build_client(true)
         .out_dir(generated_dir_path)
+        // Keeps the oneof small next to the not-found flag.
+        .boxed(".news.BatchGetArticleResult.result.article")
         .compile_protos(&proto_paths, &["../proto"])?;
 
     let out_dir = format!("../{}", generated_dir_path);
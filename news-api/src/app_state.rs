@@ -1,7 +1,13 @@
+use crate::content_filter::ContentFilter;
+use crate::media_storage::{build_media_storage, MediaStorage};
+use crate::related_cache::RelatedCache;
 use crate::settings::Settings;
+use crate::view_recorder::ViewRecorder;
 use anyhow::{Context, Result};
 use diesel::r2d2::{ConnectionManager, Pool, PooledConnection};
 use diesel::PgConnection;
+use std::collections::HashSet;
+use std::net::IpAddr;
 use std::sync::Arc;
 
 pub struct DbPool {
@@ -32,12 +38,37 @@ impl DbPool {
 pub struct AppState {
     pub db_pool: Arc<DbPool>,
     pub settings: Arc<Settings>,
+    pub media_storage: Arc<dyn MediaStorage>,
+    pub view_recorder: Arc<ViewRecorder>,
+    pub content_filter: Arc<ContentFilter>,
+    pub related_cache: Arc<RelatedCache>,
+    pub trusted_proxies: Arc<HashSet<IpAddr>>,
 }
 
 impl AppState {
     pub fn new(settings: Arc<Settings>) -> Result<Self> {
         let db_pool = Arc::new(DbPool::new(&settings.database.uri)?);
+        let media_storage = build_media_storage(&settings.media)?;
+        let view_recorder = Arc::new(ViewRecorder::new(
+            settings.jobs.get_view_dedup_window(),
+            settings.jobs.view_dedup_max_viewers,
+        ));
+        let content_filter = Arc::new(ContentFilter::new(
+            &settings.content_filter.rules_path,
+        )?);
+        let related_cache = Arc::new(RelatedCache::new(
+            settings.app.get_related_cache_ttl(),
+        ));
+        let trusted_proxies = Arc::new(settings.app.get_trusted_proxies()?);
 
-        Ok(Self { db_pool, settings })
+        Ok(Self {
+            db_pool,
+            settings,
+            media_storage,
+            view_recorder,
+            content_filter,
+            related_cache,
+            trusted_proxies,
+        })
     }
 }
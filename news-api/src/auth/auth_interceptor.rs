@@ -1,17 +1,25 @@
 use crate::app_state::AppState;
 use crate::consts::{SessionId, UserId, AUTHORIZE_HEADER, REQUEST_PATH_HEADER};
 use crate::infrastructure::get_session_by_id;
+use std::collections::HashSet;
+use std::sync::Arc;
 use tonic::service::Interceptor;
 use tonic::{Request, Status};
 
 #[derive(Clone)]
 pub struct AuthInterceptor {
     app_state: AppState,
+    secure_routes: Arc<HashSet<String>>,
 }
 
 impl AuthInterceptor {
     pub fn new(app_state: AppState) -> Self {
-        Self { app_state }
+        let secure_routes = Arc::new(app_state.settings.auth.get_secure_routes());
+
+        Self {
+            app_state,
+            secure_routes,
+        }
     }
 }
 
@@ -24,28 +32,36 @@ impl Interceptor for AuthInterceptor {
             .and_then(|value| value.to_str().ok())
             .ok_or_else(|| Status::unauthenticated("No RPC metadata or invalid path format"))?;
 
-        if self
-            .app_state
-            .settings
-            .auth
-            .secure_routes
-            .contains(request_path)
-        {
+        // Public routes leave the session to the handlers that personalise their responses
+        // (e.g. show drafts to their author), a malformed header counts as no session there.
+        if !self.secure_routes.contains(request_path) {
             let session_id = metadata
                 .get(AUTHORIZE_HEADER)
-                .ok_or(Status::unauthenticated("Invalid token"))?
-                .to_str()
-                .map_err(|_| Status::unauthenticated("Invalid token"))?;
-
-            let user_id = get_session_by_id(&self.app_state.db_pool, session_id)
-                .map_err(|_| Status::unauthenticated("No such session"))?;
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string());
+            if let Some(session_id) = session_id {
+                request
+                    .extensions_mut()
+                    .insert(SessionId { value: session_id });
+            }
 
-            let sid_string = session_id.to_string();
-            let extensions = request.extensions_mut();
-            extensions.insert(UserId { value: user_id.id });
-            extensions.insert(SessionId { value: sid_string });
+            return Ok(request);
         }
 
+        let session_id = metadata
+            .get(AUTHORIZE_HEADER)
+            .ok_or_else(|| Status::unauthenticated("Invalid token"))?
+            .to_str()
+            .map_err(|_| Status::unauthenticated("Invalid token"))?
+            .to_string();
+
+        let user_id = get_session_by_id(&self.app_state.db_pool, &session_id)
+            .map_err(|_| Status::unauthenticated("No such session"))?;
+
+        let extensions = request.extensions_mut();
+        extensions.insert(UserId { value: user_id.id });
+        extensions.insert(SessionId { value: session_id });
+
         Ok(request)
     }
 }
@@ -7,5 +7,5 @@ pub struct SessionId {
 }
 #[derive(Clone)]
 pub struct UserId {
-    pub value: i32,
+    pub value: i64,
 }
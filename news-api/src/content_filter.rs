@@ -0,0 +1,264 @@
+use anyhow::{Context, Result};
+use config::{Config, File, FileFormat};
+use diesel::internal::derives::multiconnection::chrono::TimeDelta;
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+use std::fs;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::SystemTime;
+
+/// What a rule makes of the content it matches, from the mildest.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    #[default]
+    Allow,
+    /// Accepted but hidden until a moderator approves it.
+    Hold,
+    Reject,
+}
+
+/// Verdict of the strictest rule the content matched, `None` names no rule when all allow it.
+#[derive(Debug, Default)]
+pub struct FilterOutcome {
+    pub verdict: Verdict,
+    pub rule: Option<String>,
+}
+
+/// Content being written with what the filter needs to know about its author.
+pub struct Submission<'a> {
+    pub text: &'a str,
+    /// Whether other live content has the same hash.
+    pub duplicate: bool,
+    /// Posts of the author within the rate limit window, `None` when not rate limited.
+    pub recent_posts: Option<i64>,
+}
+
+/// The rules file as written, every rule is optional.
+#[derive(Deserialize, Default)]
+struct RulesFile {
+    banned_words: Option<BannedWordsRule>,
+    #[serde(default)]
+    patterns: Vec<PatternRule>,
+    links: Option<LinksRule>,
+    duplicates: Option<DuplicatesRule>,
+    rate_limit: Option<RateLimitRule>,
+}
+
+#[derive(Deserialize)]
+struct BannedWordsRule {
+    words: Vec<String>,
+    verdict: Verdict,
+}
+
+#[derive(Deserialize)]
+struct PatternRule {
+    name: String,
+    pattern: String,
+    verdict: Verdict,
+}
+
+#[derive(Deserialize)]
+struct LinksRule {
+    max_links: usize,
+    verdict: Verdict,
+}
+
+#[derive(Deserialize)]
+struct DuplicatesRule {
+    verdict: Verdict,
+}
+
+#[derive(Deserialize)]
+struct RateLimitRule {
+    max_posts: i64,
+    window_mins: i64,
+    verdict: Verdict,
+}
+
+/// Compiled rules, checked in the order of the rules file.
+pub struct FilterRules {
+    banned_words: Option<(Regex, Verdict)>,
+    patterns: Vec<(String, Regex, Verdict)>,
+    links: Option<(Regex, usize, Verdict)>,
+    duplicates: Option<Verdict>,
+    rate_limit: Option<RateLimitRule>,
+}
+
+impl FilterRules {
+    fn compile(rules: RulesFile) -> Result<Self> {
+        let banned_words = match rules.banned_words {
+            Some(rule) => {
+                let words = rule
+                    .words
+                    .iter()
+                    .map(|word| word.trim())
+                    .filter(|word| !word.is_empty())
+                    .map(regex::escape)
+                    .collect::<Vec<_>>();
+
+                match words.is_empty() {
+                    true => None,
+                    false => {
+                        let regex = RegexBuilder::new(&format!(r"\b(?:{})\b", words.join("|")))
+                            .case_insensitive(true)
+                            .build()
+                            .context("invalid banned words")?;
+
+                        Some((regex, rule.verdict))
+                    }
+                }
+            }
+            None => None,
+        };
+        let patterns = rules
+            .patterns
+            .into_iter()
+            .map(|rule| {
+                let regex = Regex::new(&rule.pattern)
+                    .with_context(|| format!("invalid pattern of rule {}", rule.name))?;
+
+                anyhow::Ok((rule.name, regex, rule.verdict))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let links = rules
+            .links
+            .map(|rule| {
+                let regex = Regex::new(r"(?i)\b(?:https?://|www\.)")?;
+
+                anyhow::Ok((regex, rule.max_links, rule.verdict))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            banned_words,
+            patterns,
+            links,
+            duplicates: rules.duplicates.map(|rule| rule.verdict),
+            rate_limit: rules.rate_limit,
+        })
+    }
+
+    /// Whether the submission has to say if the content is a duplicate.
+    pub fn checks_duplicates(&self) -> bool {
+        self.duplicates.is_some()
+    }
+
+    /// Window the author's recent posts are counted in, `None` without a rate limit.
+    pub fn rate_limit_window(&self) -> Option<TimeDelta> {
+        self.rate_limit
+            .as_ref()
+            .map(|rule| TimeDelta::minutes(rule.window_mins))
+    }
+
+    pub fn evaluate(&self, submission: &Submission) -> FilterOutcome {
+        let mut outcome = FilterOutcome::default();
+        let mut apply = |matched: bool, rule: &str, verdict: Verdict| {
+            if matched && verdict > outcome.verdict {
+                outcome = FilterOutcome {
+                    verdict,
+                    rule: Some(rule.to_string()),
+                };
+            }
+        };
+
+        if let Some((regex, verdict)) = &self.banned_words {
+            apply(
+                regex.is_match(submission.text),
+                "banned_words",
+                *verdict,
+            );
+        }
+        for (name, regex, verdict) in &self.patterns {
+            apply(regex.is_match(submission.text), name, *verdict);
+        }
+        if let Some((regex, max_links, verdict)) = &self.links {
+            let links = regex.find_iter(submission.text).count();
+            apply(links > *max_links, "links", *verdict);
+        }
+        if let Some(verdict) = self.duplicates {
+            apply(submission.duplicate, "duplicates", verdict);
+        }
+        if let (Some(rule), Some(recent_posts)) = (&self.rate_limit, submission.recent_posts) {
+            apply(
+                recent_posts >= rule.max_posts,
+                "rate_limit",
+                rule.verdict,
+            );
+        }
+
+        outcome
+    }
+}
+
+/// Rules loaded from a TOML file, picked up again whenever the file changes so they can be tuned
+/// without a restart. A missing file means no rules.
+pub struct ContentFilter {
+    rules_path: String,
+    loaded: RwLock<LoadedRules>,
+}
+
+struct LoadedRules {
+    rules: Arc<FilterRules>,
+    modified: Option<SystemTime>,
+}
+
+impl ContentFilter {
+    pub fn new(rules_path: &str) -> Result<Self> {
+        let modified = modified_at(rules_path);
+        let rules = load_rules(rules_path)?;
+
+        Ok(Self {
+            rules_path: rules_path.to_string(),
+            loaded: RwLock::new(LoadedRules {
+                rules: Arc::new(rules),
+                modified,
+            }),
+        })
+    }
+
+    pub fn rules(&self) -> Arc<FilterRules> {
+        let loaded = self.loaded.read().unwrap_or_else(PoisonError::into_inner);
+
+        loaded.rules.clone()
+    }
+
+    /// Reloads the rules if the file changed since they were loaded, tells whether it did. Rules
+    /// that fail to load leave the current ones in place.
+    pub fn reload(&self) -> Result<bool> {
+        let modified = modified_at(&self.rules_path);
+        let loaded_modified = self
+            .loaded
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .modified;
+
+        if modified == loaded_modified {
+            return Ok(false);
+        }
+
+        let rules = load_rules(&self.rules_path);
+        let mut loaded = self.loaded.write().unwrap_or_else(PoisonError::into_inner);
+        // Not retried until the file changes again.
+        loaded.modified = modified;
+        loaded.rules = Arc::new(rules?);
+
+        Ok(true)
+    }
+}
+
+fn modified_at(rules_path: &str) -> Option<SystemTime> {
+    fs::metadata(rules_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn load_rules(rules_path: &str) -> Result<FilterRules> {
+    let rules = Config::builder()
+        .add_source(File::new(rules_path, FileFormat::Toml).required(false))
+        .build()
+        .and_then(Config::try_deserialize::<RulesFile>)
+        .with_context(|| format!("[news-api] [content-filter] failed to load {rules_path}"))?;
+
+    FilterRules::compile(rules)
+}
@@ -0,0 +1,260 @@
+use crate::media_processing::MEDIA_URL_SCHEME;
+use ammonia::Builder;
+use pulldown_cmark::{html, Options, Parser};
+use std::collections::HashSet;
+
+const EXCERPT_LENGTH: usize = 200;
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Tags kept by the sanitizer, everything else is stripped with its attributes.
+const ALLOWED_TAGS: [&str; 31] = [
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "code",
+    "del",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "s",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+];
+
+/// Forms of the article content derived from its source, cached next to it.
+pub struct RenderedContent {
+    pub html: String,
+    pub excerpt: String,
+    pub reading_time_minutes: i32,
+}
+
+/// Renders the content source of the given format (`plain`, `markdown` or `html`) into sanitized
+/// HTML and derives the plain-text excerpt and reading time from it.
+pub fn render_content(content: &str, content_format: &str) -> RenderedContent {
+    let html = match content_format {
+        "markdown" => sanitize_html(&render_markdown(content)),
+        "html" => sanitize_html(content),
+        _ => render_plain(content),
+    };
+
+    let text = html_to_text(&html);
+
+    RenderedContent {
+        excerpt: build_excerpt(&text),
+        reading_time_minutes: estimate_reading_time(&text),
+        html,
+    }
+}
+
+fn render_markdown(content: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+    let mut html = String::with_capacity(content.len());
+
+    html::push_html(&mut html, Parser::new_ext(content, options));
+
+    html
+}
+
+fn sanitize_html(html: &str) -> String {
+    Builder::default()
+        .tags(HashSet::from(ALLOWED_TAGS))
+        .add_url_schemes(&[MEDIA_URL_SCHEME])
+        .link_rel(Some("noopener noreferrer nofollow"))
+        .clean(html)
+        .to_string()
+}
+
+/// Blank lines separate paragraphs, single line breaks are kept as `<br>`.
+fn render_plain(content: &str) -> String {
+    content
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| {
+            format!(
+                "<p>{}</p>",
+                escape_html(paragraph).replace('\n', "<br>")
+            )
+        })
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Strips tags from sanitized HTML (attribute values are always double-quoted there), every tag
+/// boundary becomes whitespace.
+fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    let mut in_attribute = false;
+
+    for c in html.chars() {
+        match c {
+            '<' if !in_tag => in_tag = true,
+            '"' if in_tag => in_attribute = !in_attribute,
+            '>' if in_tag && !in_attribute => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn build_excerpt(text: &str) -> String {
+    let mut excerpt = String::with_capacity(EXCERPT_LENGTH + 3);
+
+    for word in text.split_whitespace() {
+        if excerpt.chars().count() + word.chars().count() + 1 > EXCERPT_LENGTH {
+            if excerpt.is_empty() {
+                excerpt.extend(word.chars().take(EXCERPT_LENGTH));
+            }
+            excerpt.push('…');
+            return excerpt;
+        }
+
+        if !excerpt.is_empty() {
+            excerpt.push(' ');
+        }
+        excerpt.push_str(word);
+    }
+
+    excerpt
+}
+
+fn estimate_reading_time(text: &str) -> i32 {
+    let words = text.split_whitespace().count();
+
+    words.div_ceil(WORDS_PER_MINUTE) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitizer_strips_scripts() {
+        let html = sanitize_html(r#"<p>Hi<script>alert(1)</script></p>"#);
+
+        assert_eq!(html, "<p>Hi</p>");
+    }
+
+    #[test]
+    fn sanitizer_strips_event_handlers() {
+        let html = sanitize_html(r#"<img src="media:1" onerror="alert(1)">"#);
+
+        assert!(!html.contains("onerror"), "{html}");
+        assert!(!html.contains("alert"), "{html}");
+    }
+
+    #[test]
+    fn sanitizer_strips_javascript_urls() {
+        let html = sanitize_html(r#"<a href="javascript:alert(1)">link</a>"#);
+
+        assert!(!html.contains("javascript:"), "{html}");
+        assert!(html.contains("link"), "{html}");
+    }
+
+    #[test]
+    fn sanitizer_keeps_media_urls() {
+        let media_url = "media:0b5f2e4c-7d5c-4a57-9b0e-0d4e5a2b1c3d";
+
+        let html = sanitize_html(&format!(
+            r#"<img src="{media_url}"><a href="{media_url}">cover</a>"#
+        ));
+
+        assert!(
+            html.contains(&format!(r#"<img src="{media_url}">"#)),
+            "{html}"
+        );
+        assert!(
+            html.contains(&format!(r#"href="{media_url}""#)),
+            "{html}"
+        );
+    }
+
+    #[test]
+    fn markdown_is_sanitized() {
+        let rendered = render_content("Hi <script>alert(1)</script>", "markdown");
+
+        assert!(
+            !rendered.html.contains("<script"),
+            "{}",
+            rendered.html
+        );
+    }
+
+    #[test]
+    fn text_skips_angle_brackets_inside_attributes() {
+        let text = html_to_text(r#"<p title="a > b">Hello</p><img alt="x>y">World"#);
+
+        assert_eq!(
+            text.split_whitespace().collect::<Vec<_>>(),
+            ["Hello", "World"]
+        );
+    }
+
+    #[test]
+    fn text_unescapes_entities() {
+        let text = html_to_text("<p>&lt;b&gt; &amp;amp; &quot;q&quot;</p>");
+
+        assert_eq!(text.trim(), r#"<b> &amp; "q""#);
+    }
+
+    #[test]
+    fn short_excerpts_are_kept_whole() {
+        assert_eq!(build_excerpt("Grüße aus  Köln"), "Grüße aus Köln");
+    }
+
+    #[test]
+    fn excerpts_cut_multi_byte_text_between_words() {
+        let text = "ääää ".repeat(100);
+
+        let excerpt = build_excerpt(&text);
+
+        assert!(excerpt.ends_with("ääää…"), "{excerpt}");
+        assert_eq!(excerpt.chars().count(), 200);
+    }
+
+    #[test]
+    fn excerpts_cut_long_multi_byte_words_at_a_char_boundary() {
+        let text = "日本語".repeat(100);
+
+        let excerpt = build_excerpt(&text);
+
+        assert_eq!(excerpt.chars().count(), EXCERPT_LENGTH + 1);
+        assert!(excerpt.ends_with('…'), "{excerpt}");
+    }
+}
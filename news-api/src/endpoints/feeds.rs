@@ -0,0 +1,235 @@
+use crate::app_state::AppState;
+use crate::feed_render::{render_atom, render_rss, Feed};
+use crate::infrastructure::{
+    get_articles_page, get_feed_last_modified, ArticleFilter, ArticleOrder,
+};
+use crate::tags::normalize_tag;
+use axum::extract::{Path, State};
+use axum::http::header::{CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use axum::http::{HeaderMap, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use diesel::internal::derives::multiconnection::chrono::{DateTime, NaiveDateTime};
+use sha2::{Digest, Sha256};
+use tokio::net::TcpListener;
+
+const HTTP_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+#[derive(Clone, Copy)]
+enum FeedFormat {
+    Rss,
+    Atom,
+}
+
+impl FeedFormat {
+    fn from_file_name(file_name: &str) -> Option<Self> {
+        match file_name {
+            "feed.rss" => Some(FeedFormat::Rss),
+            "feed.atom" => Some(FeedFormat::Atom),
+            _ => None,
+        }
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+        }
+    }
+}
+
+/// Serves the feeds until the listener fails, the gRPC server keeps running either way.
+pub async fn serve_feeds(app_state: AppState, listener: TcpListener) {
+    let router = Router::new()
+        .route("/:file_name", get(site_feed))
+        .route("/tags/:tag/:file_name", get(tag_feed))
+        .route("/authors/:username/:file_name", get(author_feed))
+        .with_state(app_state);
+
+    if let Err(err) = axum::serve(listener, router).await {
+        eprintln!("[news-api] [feeds] server failed: {err:#}");
+    }
+}
+
+async fn site_feed(
+    State(app_state): State<AppState>,
+    Path(file_name): Path<String>,
+    uri: Uri,
+    headers: HeaderMap,
+) -> Response {
+    serve_feed(
+        &app_state,
+        &file_name,
+        &uri,
+        &headers,
+        None,
+        ArticleFilter::default(),
+    )
+}
+
+async fn tag_feed(
+    State(app_state): State<AppState>,
+    Path((tag, file_name)): Path<(String, String)>,
+    uri: Uri,
+    headers: HeaderMap,
+) -> Response {
+    // A blank tag would not filter at all.
+    let Some(tag) = normalize_tag(&tag) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    serve_feed(
+        &app_state,
+        &file_name,
+        &uri,
+        &headers,
+        Some(format!("#{tag}")),
+        ArticleFilter {
+            tags: vec![tag],
+            ..Default::default()
+        },
+    )
+}
+
+async fn author_feed(
+    State(app_state): State<AppState>,
+    Path((username, file_name)): Path<(String, String)>,
+    uri: Uri,
+    headers: HeaderMap,
+) -> Response {
+    serve_feed(
+        &app_state,
+        &file_name,
+        &uri,
+        &headers,
+        Some(username.clone()),
+        ArticleFilter {
+            author_username: Some(username),
+            ..Default::default()
+        },
+    )
+}
+
+/// Newest published articles matching the filter, the same ones `GetArticles` lists first to
+/// anonymous callers.
+fn serve_feed(
+    app_state: &AppState,
+    file_name: &str,
+    uri: &Uri,
+    headers: &HeaderMap,
+    subtitle: Option<String>,
+    filter: ArticleFilter,
+) -> Response {
+    let (Some(format), Some(settings)) = (
+        FeedFormat::from_file_name(file_name),
+        app_state.settings.app.feeds.as_ref(),
+    ) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    // Taken before the articles, a change in between then makes the next request load them again
+    // rather than leaving the reader with a copy older than its date.
+    let last_modified = match get_feed_last_modified(
+        &app_state.db_pool,
+        filter.tags.first().map(String::as_str),
+        filter.author_username.as_deref(),
+    ) {
+        Ok(last_modified) => last_modified,
+        Err(err) => {
+            eprintln!("[news-api] [feeds] failed to load the last change: {err:#}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let articles = match get_articles_page(
+        &app_state.db_pool,
+        ArticleOrder::Newest,
+        None,
+        settings.max_items,
+        filter,
+        None,
+    ) {
+        Ok(articles) => articles,
+        Err(err) => {
+            eprintln!("[news-api] [feeds] failed to load articles: {err:#}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let articles = articles
+        .into_iter()
+        .map(|entry| entry.article)
+        .collect::<Vec<_>>();
+
+    let title = match subtitle {
+        Some(subtitle) => format!("{} - {subtitle}", settings.title),
+        None => settings.title.clone(),
+    };
+    let feed = Feed {
+        title: &title,
+        site_url: settings.site_url.trim_end_matches('/'),
+        path: uri.path(),
+        articles: &articles,
+        last_modified,
+    };
+    let body = match format {
+        FeedFormat::Rss => render_rss(&feed),
+        FeedFormat::Atom => render_atom(&feed),
+    };
+
+    // The body only changes with the articles, so its hash tells whether the reader's copy is
+    // still current, edits included.
+    let etag = format!(
+        "\"{}\"",
+        hex::encode(&Sha256::digest(&body)[..16])
+    );
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(
+        ETAG,
+        etag.parse().expect("hex etag is a valid header"),
+    );
+    if let Some(last_modified) = last_modified {
+        let last_modified = last_modified.format(HTTP_DATE_FORMAT).to_string();
+        response_headers.insert(
+            LAST_MODIFIED,
+            last_modified.parse().expect("http date is a valid header"),
+        );
+    }
+
+    if is_not_modified(headers, &etag, last_modified) {
+        return (StatusCode::NOT_MODIFIED, response_headers).into_response();
+    }
+
+    response_headers.insert(
+        CONTENT_TYPE,
+        format.content_type().parse().expect("valid mime"),
+    );
+
+    (response_headers, body).into_response()
+}
+
+/// Whether the reader's copy is current, `If-None-Match` wins over `If-Modified-Since` when both
+/// are sent.
+fn is_not_modified(headers: &HeaderMap, etag: &str, last_modified: Option<NaiveDateTime>) -> bool {
+    if let Some(if_none_match) = headers.get(IF_NONE_MATCH) {
+        return if_none_match.to_str().is_ok_and(|if_none_match| {
+            if_none_match
+                .split(',')
+                .map(|tag| tag.trim())
+                .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+        });
+    }
+
+    let if_modified_since = headers
+        .get(IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok());
+
+    match (if_modified_since, last_modified) {
+        // HTTP dates have no fractions of a second.
+        (Some(if_modified_since), Some(last_modified)) => {
+            last_modified.and_utc().timestamp() <= if_modified_since.timestamp()
+        }
+        _ => false,
+    }
+}
@@ -1,12 +1,500 @@
+// Handlers and their helpers fail with tonic's `Status`, which is large (176 bytes) but what
+// every RPC has to return, so boxing it would only move the conversion into each handler.
+#![allow(clippy::result_large_err)]
+
 use crate::app_state::AppState;
+use crate::errors::{
+    article_error_status, bookmark_error_status, contributor_error_status, moderation_error_status,
+    section_error_status, tag_error_status,
+};
 use crate::infrastructure::{
-    create_article, delete_article, get_article, get_articles_page, update_article,
+    accept_contributor_invitation, add_bookmark, create_article, create_media, create_reading_list,
+    create_section, decline_contributor_invitation, delete_article, delete_reading_list,
+    get_article, get_article_bodies, get_article_by_slug, get_article_key, get_article_revision,
+    get_article_revisions, get_article_stats, get_articles_by_ids, get_articles_page,
+    get_bookmarks_page, get_contributor_invitations, get_contributors, get_deleted_articles,
+    get_media, get_reading_lists, get_related_article_ids, get_reports_page, get_sections,
+    get_session_by_id, get_tags_page, get_user_role, invite_contributor, merge_sections,
+    merge_tags, moderate_report, move_section, publish_article, remove_bookmark,
+    remove_contributor, rename_tag, reorder_contributors, reorder_reading_lists, report_article,
+    report_comment, resolve_translation, restore_article, suggest_tags, transfer_article_ownership,
+    unpublish_article, update_article, update_section, ArticleChanges, ArticleFilter, ArticleOrder,
+    NewArticle, NewMedia, NewSection,
 };
-use crate::mappers::{into_article, into_articles};
+use crate::languages::{normalize_language, DEFAULT_LANGUAGE};
+use crate::mappers::{
+    fill_article_body, from_article_status, from_content_format, from_contributor_role,
+    from_moderation_action, from_report_reason, from_report_status, into_article,
+    into_article_revision, into_article_revisions, into_article_stats_bucket, into_article_status,
+    into_article_summary, into_bookmark, into_contributor, into_contributor_invitation,
+    into_contributors, into_legacy_id, into_media, into_optional_timestamp, into_reading_list,
+    into_reading_lists, into_report, into_reports, into_section, into_sections, into_tag,
+    into_tags,
+};
+use crate::media_processing::{process_image, sniff_content_type};
 use crate::news_generated::news_service_server::NewsService;
 use crate::news_generated::*;
-use crate::utils::{get_user_id, parse_timestamp};
-use tonic::{Request, Response, Status};
+use crate::pagination::{
+    page_limit, resolve_page_size, PageCursor, PageDirection, PageTokens, SortKey,
+};
+use crate::revision_diff::{diff_tags, diff_text};
+use crate::slug::slugify;
+use crate::tags::{normalize_tag, normalize_tags};
+use crate::utils::{get_optional_session_id, get_optional_user_id, get_user_id, parse_timestamp};
+use db_schema::models::{ArticleEntry, ArticleSummaryEntry};
+use diesel::internal::derives::multiconnection::chrono::{NaiveDateTime, TimeDelta, Utc};
+use prost_types::FieldMask;
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::pin::Pin;
+use tokio::task::spawn_blocking;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status, Streaming};
+use uuid::Uuid;
+
+const MEDIA_CHUNK_SIZE: usize = 64 * 1024;
+const MAX_FILE_NAME_LENGTH: usize = 255;
+const DEFAULT_ARTICLES_PAGE_SIZE: i64 = 20;
+const MAX_ARTICLES_PAGE_SIZE: i64 = 100;
+const MAX_BATCH_ARTICLES: usize = 100;
+const DEFAULT_RELATED_ARTICLES: i64 = 5;
+const MAX_RELATED_ARTICLES: i64 = 20;
+const MAX_STATS_BUCKETS: i64 = 1000;
+const DEFAULT_TAGS_PAGE_SIZE: i64 = 50;
+const MAX_TAGS_PAGE_SIZE: i64 = 100;
+const DEFAULT_TAG_SUGGESTIONS: i64 = 10;
+const MAX_TAG_SUGGESTIONS: i64 = 50;
+const TAGS_PAGE_SCOPE: &str = "tags";
+const BOOKMARKS_PAGE_SCOPE: &str = "bookmarks";
+const MAX_READING_LIST_NAME_LENGTH: usize = 100;
+const ADMIN_ROLE: &str = "admin";
+const EDITOR_ROLE: &str = "editor";
+const MODERATOR_ROLE: &str = "moderator";
+const MAX_SECTION_NAME_LENGTH: usize = 100;
+const REPORTS_PAGE_SCOPE: &str = "reports";
+const MAX_REPORT_DETAILS_LENGTH: usize = 1000;
+const MAX_MODERATION_REASON_LENGTH: usize = 1000;
+
+/// Resolves the requested publication time into `published` (now or in the past)
+/// or `scheduled` (in the future).
+fn resolve_publication(publish_at: &str) -> Result<(ArticleStatus, NaiveDateTime), Status> {
+    let now = Utc::now().naive_utc();
+
+    if publish_at.is_empty() {
+        return Ok((ArticleStatus::Published, now));
+    }
+
+    let publish_at = parse_timestamp(publish_at)
+        .ok_or_else(|| Status::invalid_argument("Invalid publish_at timestamp"))?;
+
+    match publish_at > now {
+        true => Ok((ArticleStatus::Scheduled, publish_at)),
+        false => Ok((ArticleStatus::Published, now)),
+    }
+}
+
+/// Parses an optional media id, empty means none.
+fn parse_media_id(media_id: &str) -> Result<Option<Uuid>, Status> {
+    parse_optional_uuid(media_id, "Invalid media id")
+}
+
+/// Identifies the viewer for view deduplication: the signed-in user, otherwise the client address.
+/// Behind trusted proxies that is the last `x-forwarded-for` hop they didn't add themselves, the
+/// header of anyone else is ignored as it can be made up.
+fn viewer_key<T>(
+    request: &Request<T>,
+    viewer_id: Option<i64>,
+    trusted_proxies: &HashSet<IpAddr>,
+) -> Option<String> {
+    if let Some(viewer_id) = viewer_id {
+        return Some(format!("user:{viewer_id}"));
+    }
+
+    let remote_addr = request.remote_addr()?.ip();
+    if !trusted_proxies.contains(&remote_addr) {
+        return Some(format!("addr:{remote_addr}"));
+    }
+
+    let forwarded_for = request
+        .metadata()
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|address| address.trim().parse::<IpAddr>())
+        .collect::<Vec<_>>();
+
+    // Walks back from the nearest hop, an unparsable one ends the part that can be believed.
+    let mut client = remote_addr;
+    for address in forwarded_for.into_iter().rev() {
+        let Ok(address) = address else {
+            break;
+        };
+        client = address;
+        if !trusted_proxies.contains(&address) {
+            break;
+        }
+    }
+
+    Some(format!("addr:{client}"))
+}
+
+/// Resolves the requested feed order and the page token scope of it, tokens of one order (and
+/// top window) don't apply to another.
+fn resolve_article_order(sort: ArticleSort, top_window: TopWindow) -> (ArticleOrder, &'static str) {
+    let now = Utc::now().naive_utc();
+
+    match (sort, top_window) {
+        (ArticleSort::Unspecified | ArticleSort::Newest, _) => (ArticleOrder::Newest, "articles"),
+        (ArticleSort::Trending, _) => (ArticleOrder::Trending, "articles/trending"),
+        (ArticleSort::Top, TopWindow::Day) => (
+            ArticleOrder::Top {
+                published_after: Some(now - TimeDelta::days(1)),
+            },
+            "articles/top/day",
+        ),
+        (ArticleSort::Top, TopWindow::Unspecified | TopWindow::Week) => (
+            ArticleOrder::Top {
+                published_after: Some(now - TimeDelta::weeks(1)),
+            },
+            "articles/top/week",
+        ),
+        (ArticleSort::Top, TopWindow::Month) => (
+            ArticleOrder::Top {
+                published_after: Some(now - TimeDelta::days(30)),
+            },
+            "articles/top/month",
+        ),
+        (ArticleSort::Top, TopWindow::All) => (
+            ArticleOrder::Top {
+                published_after: None,
+            },
+            "articles/top/all",
+        ),
+    }
+}
+
+/// Parses an optional public id, empty means none.
+fn parse_optional_uuid(id: &str, message: &str) -> Result<Option<Uuid>, Status> {
+    if id.is_empty() {
+        return Ok(None);
+    }
+
+    Uuid::parse_str(id)
+        .map(Some)
+        .map_err(|_| Status::invalid_argument(message))
+}
+
+fn parse_reading_list_id(reading_list_id: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(reading_list_id).map_err(|_| {
+        Status::invalid_argument(format!(
+            "Invalid reading_list_id: {reading_list_id}"
+        ))
+    })
+}
+
+fn parse_user_id(user_id: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(user_id)
+        .map_err(|_| Status::invalid_argument(format!("Invalid user_id: {user_id}")))
+}
+
+fn parse_comment_id(comment_id: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(comment_id)
+        .map_err(|_| Status::invalid_argument(format!("Invalid comment_id: {comment_id}")))
+}
+
+/// Trims the details of a report, optional but limited in length.
+fn parse_report_details(details: &str) -> Result<&str, Status> {
+    let details = details.trim();
+
+    if details.chars().count() > MAX_REPORT_DETAILS_LENGTH {
+        return Err(Status::invalid_argument(format!(
+            "Report details are longer than {MAX_REPORT_DETAILS_LENGTH} characters"
+        )));
+    }
+
+    Ok(details)
+}
+
+fn parse_section_id(section_id: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(section_id)
+        .map_err(|_| Status::invalid_argument(format!("Invalid section_id: {section_id}")))
+}
+
+/// Trims a section name, required and limited in length.
+fn parse_section_name(name: &str) -> Result<&str, Status> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(Status::invalid_argument("Missing section name"));
+    }
+    if name.chars().count() > MAX_SECTION_NAME_LENGTH {
+        return Err(Status::invalid_argument(format!(
+            "Section name is longer than {MAX_SECTION_NAME_LENGTH} characters"
+        )));
+    }
+
+    Ok(name)
+}
+
+/// Lowercases a section path and drops the slashes around it, `/World/Europe/` is `world/europe`.
+fn normalize_section_path(path: &str) -> String {
+    path.trim().trim_matches('/').to_lowercase()
+}
+
+/// Normalizes an optional language code, empty means none.
+fn parse_optional_language(language: &str) -> Result<Option<String>, Status> {
+    if language.trim().is_empty() {
+        return Ok(None);
+    }
+
+    normalize_language(language)
+        .map(Some)
+        .ok_or_else(|| Status::invalid_argument(format!("Invalid language: {language}")))
+}
+
+/// Parses an optional timestamp, empty means none.
+fn parse_optional_timestamp(
+    timestamp: &str,
+    message: &str,
+) -> Result<Option<NaiveDateTime>, Status> {
+    if timestamp.is_empty() {
+        return Ok(None);
+    }
+
+    parse_timestamp(timestamp)
+        .map(Some)
+        .ok_or_else(|| Status::invalid_argument(message))
+}
+
+/// Narrows the requested changes to the fields named by the update mask, a missing, empty or `*`
+/// mask keeps all of them.
+fn resolve_article_changes(
+    update_mask: Option<FieldMask>,
+    requested: ArticleChanges,
+) -> Result<ArticleChanges, Status> {
+    let paths = update_mask.map(|mask| mask.paths).unwrap_or_default();
+
+    // Format, cover, section and language are kept unless given, clients unaware of them don't
+    // reset them.
+    if paths.is_empty() || paths.iter().any(|path| path == "*") {
+        return Ok(requested);
+    }
+
+    let mut changes = ArticleChanges {
+        title: None,
+        content: None,
+        content_format: None,
+        tags: None,
+        cover_media_id: None,
+        section_id: None,
+        language: None,
+    };
+
+    for path in paths {
+        match path.as_str() {
+            "title" => changes.title = requested.title.clone(),
+            "content" => changes.content = requested.content.clone(),
+            "content_format" => {
+                changes.content_format = Some(
+                    requested
+                        .content_format
+                        .clone()
+                        .ok_or_else(|| Status::invalid_argument("Missing content_format"))?,
+                )
+            }
+            "tags" => changes.tags = requested.tags.clone(),
+            // Naming the cover in the mask is the one way to remove it.
+            "cover_media_id" => changes.cover_media_id = Some(requested.cover_media_id.flatten()),
+            "section_id" => {
+                changes.section_id = Some(
+                    requested
+                        .section_id
+                        .ok_or_else(|| Status::invalid_argument("Missing section_id"))?,
+                )
+            }
+            "language" => {
+                changes.language = Some(
+                    requested
+                        .language
+                        .clone()
+                        .ok_or_else(|| Status::invalid_argument("Missing language"))?,
+                )
+            }
+            _ => {
+                return Err(Status::invalid_argument(format!(
+                    "Unknown update_mask path: {path}"
+                )))
+            }
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Article id of a batch request, all of them are resolved together in one query.
+enum ArticleRef {
+    Public(Uuid),
+    Legacy(i64),
+}
+
+impl AppState {
+    /// The signed-in caller, on public routes the session is only looked up here. Unknown sessions
+    /// count as anonymous.
+    fn viewer_id<T>(&self, request: &Request<T>) -> Option<i64> {
+        if let Some(user_id) = get_optional_user_id(request) {
+            return Some(user_id.value);
+        }
+
+        let session_id = get_optional_session_id(request)?;
+
+        get_session_by_id(&self.db_pool, &session_id.value)
+            .ok()
+            .map(|user_id| user_id.id)
+    }
+
+    /// Page tokens of the given list and filter, signed with a key derived from the server secret.
+    fn page_tokens(&self, scope: &'static str, filter: &str) -> PageTokens {
+        PageTokens::new(scope, filter, &self.settings.auth.secret_key)
+    }
+
+    /// Resolves a public article id into the internal key. While legacy ids are accepted, a
+    /// numeric id is taken as is, either in `article_id` or in the deprecated numeric field.
+    fn resolve_article_id(&self, article_id: &str, legacy_article_id: i32) -> Result<i64, Status> {
+        let accept_legacy_ids = self.settings.app.accept_legacy_ids;
+
+        if article_id.is_empty() {
+            return match legacy_article_id > 0 && accept_legacy_ids {
+                true => Ok(legacy_article_id.into()),
+                false => Err(Status::invalid_argument("Missing article_id")),
+            };
+        }
+
+        if let Ok(public_id) = Uuid::parse_str(article_id) {
+            return get_article_key(&self.db_pool, public_id)
+                .map_err(|err| article_error_status(err, "Article not found"));
+        }
+
+        match article_id.parse::<i64>() {
+            Ok(legacy_article_id) if legacy_article_id > 0 && accept_legacy_ids => {
+                Ok(legacy_article_id)
+            }
+            _ => Err(Status::invalid_argument("Invalid article_id")),
+        }
+    }
+
+    /// Parses an article id of a batch request by the same rules as [`Self::resolve_article_id`].
+    fn parse_article_ref(&self, article_id: &str) -> Result<ArticleRef, Status> {
+        if let Ok(public_id) = Uuid::parse_str(article_id) {
+            return Ok(ArticleRef::Public(public_id));
+        }
+
+        match article_id.parse::<i64>() {
+            Ok(legacy_article_id)
+                if legacy_article_id > 0 && self.settings.app.accept_legacy_ids =>
+            {
+                Ok(ArticleRef::Legacy(legacy_article_id))
+            }
+            _ => Err(Status::invalid_argument(format!(
+                "Invalid article_id: {article_id}"
+            ))),
+        }
+    }
+
+    /// Maps list entries into articles of the requested view, the full view loads the content of
+    /// the whole page in one more query.
+    fn load_article_views(
+        &self,
+        summaries: Vec<ArticleSummaryEntry>,
+        view: ArticleView,
+    ) -> Result<Vec<Article>, Status> {
+        let article_ids = summaries
+            .iter()
+            .map(|summary| summary.id)
+            .collect::<Vec<_>>();
+        let mut articles = summaries
+            .into_iter()
+            .map(|summary| into_article_summary(summary, self.settings.app.accept_legacy_ids))
+            .collect::<Vec<_>>();
+
+        if view != ArticleView::Full {
+            return Ok(articles);
+        }
+
+        let mut bodies = get_article_bodies(&self.db_pool, &article_ids)
+            .map_err(|_| Status::failed_precondition("Getting article content error"))?
+            .into_iter()
+            .map(|body| (body.id, body))
+            .collect::<HashMap<_, _>>();
+
+        for (article, article_id) in articles.iter_mut().zip(article_ids) {
+            if let Some(body) = bodies.remove(&article_id) {
+                fill_article_body(article, body);
+            }
+        }
+
+        Ok(articles)
+    }
+
+    /// Counts a read of a published article, authors reading their own articles don't count.
+    fn record_view(
+        &self,
+        article: &ArticleEntry,
+        viewer_id: Option<i64>,
+        viewer_key: Option<String>,
+    ) {
+        if article.status != "published" || viewer_id == Some(article.author_id) {
+            return;
+        }
+
+        self.view_recorder
+            .record(article.id, viewer_key, Utc::now().naive_utc());
+    }
+
+    /// Whether the user has the role, admins have every role.
+    fn has_role(&self, user_id: i64, role: &str) -> Result<bool, Status> {
+        let user_role = get_user_role(&self.db_pool, user_id)
+            .map_err(|_| Status::failed_precondition("Getting user role failed"))?;
+
+        Ok(user_role == role || user_role == ADMIN_ROLE)
+    }
+
+    /// Whether the viewer sees the content hidden by moderators.
+    fn is_moderator(&self, viewer_id: Option<i64>) -> Result<bool, Status> {
+        match viewer_id {
+            Some(viewer_id) => self.has_role(viewer_id, MODERATOR_ROLE),
+            None => Ok(false),
+        }
+    }
+
+    /// Requires the user to have the role, admins have every role.
+    fn require_role(&self, user_id: i64, role: &str) -> Result<(), Status> {
+        match self.has_role(user_id, role)? {
+            true => Ok(()),
+            false => Err(Status::permission_denied(format!(
+                "{role} role required"
+            ))),
+        }
+    }
+}
+
+/// Clears the content representations the caller did not ask for, keeps all of them when none
+/// are requested.
+fn retain_representations(article: &mut Article, representations: &[ContentRepresentation]) {
+    if representations.is_empty() {
+        return;
+    }
+
+    if !representations.contains(&ContentRepresentation::Source) {
+        article.content.clear();
+    }
+    if !representations.contains(&ContentRepresentation::Html) {
+        article.content_html.clear();
+    }
+    if !representations.contains(&ContentRepresentation::Excerpt) {
+        article.excerpt.clear();
+    }
+}
 
 #[tonic::async_trait]
 impl NewsService for AppState {
@@ -14,13 +502,225 @@ impl NewsService for AppState {
         &self,
         request: Request<GetArticleRequest>,
     ) -> Result<Response<GetArticleResponse>, Status> {
+        let viewer_id = self.viewer_id(&request);
+        let viewer_key = viewer_key(&request, viewer_id, &self.trusted_proxies);
         let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, req.legacy_article_id)?;
+        let preferred_languages = req
+            .preferred_languages
+            .iter()
+            .map(|language| {
+                normalize_language(language).ok_or_else(|| {
+                    Status::invalid_argument(format!("Invalid language: {language}"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let include_hidden = self.is_moderator(viewer_id)?;
+        let article_id = match preferred_languages.is_empty() {
+            true => article_id,
+            false => resolve_translation(
+                &self.db_pool,
+                article_id,
+                &preferred_languages,
+                viewer_id,
+                include_hidden,
+            )
+            .map_err(|_| Status::failed_precondition("Article not found"))?,
+        };
+        let article = get_article(
+            &self.db_pool,
+            article_id,
+            viewer_id,
+            include_hidden,
+        )
+        .map_err(|_| Status::failed_precondition("Article not found"))?;
+
+        self.record_view(&article, viewer_id, viewer_key);
 
-        let article = get_article(&self.db_pool, req.article_id)
-            .map_err(|_| Status::failed_precondition("Article not found"))?;
+        let mut article = into_article(article, self.settings.app.accept_legacy_ids);
+        retain_representations(
+            &mut article,
+            &req.representations().collect::<Vec<_>>(),
+        );
 
         Ok(Response::new(GetArticleResponse {
-            article: Some(into_article(article)),
+            article: Some(article),
+        }))
+    }
+
+    async fn batch_get_articles(
+        &self,
+        request: Request<BatchGetArticlesRequest>,
+    ) -> Result<Response<BatchGetArticlesResponse>, Status> {
+        let viewer_id = self.viewer_id(&request);
+        let req = request.into_inner();
+
+        if req.article_ids.len() > MAX_BATCH_ARTICLES {
+            return Err(Status::invalid_argument(format!(
+                "At most {MAX_BATCH_ARTICLES} article ids per batch"
+            )));
+        }
+
+        // A malformed id is reported in its own entry, the others are still looked up.
+        let article_refs = req
+            .article_ids
+            .iter()
+            .map(|article_id| self.parse_article_ref(article_id))
+            .collect::<Vec<_>>();
+
+        let mut public_ids = vec![];
+        let mut legacy_ids = vec![];
+        for article_ref in article_refs.iter().flatten() {
+            match article_ref {
+                ArticleRef::Public(public_id) => public_ids.push(*public_id),
+                ArticleRef::Legacy(legacy_id) => legacy_ids.push(*legacy_id),
+            }
+        }
+
+        let entries = get_articles_by_ids(
+            &self.db_pool,
+            &public_ids,
+            &legacy_ids,
+            viewer_id,
+            self.is_moderator(viewer_id)?,
+        )
+        .map_err(|_| Status::failed_precondition("Getting articles error"))?;
+
+        let representations = req.representations().collect::<Vec<_>>();
+        let mut public_ids_by_key = HashMap::new();
+        let mut articles = HashMap::new();
+        for entry in entries {
+            public_ids_by_key.insert(entry.id, entry.public_id);
+
+            let public_id = entry.public_id;
+            let mut article = into_article(entry, self.settings.app.accept_legacy_ids);
+            retain_representations(&mut article, &representations);
+            articles.insert(public_id, article);
+        }
+
+        let results = req
+            .article_ids
+            .into_iter()
+            .zip(article_refs)
+            .map(|(article_id, article_ref)| {
+                let public_id = match article_ref {
+                    Ok(ArticleRef::Public(public_id)) => Some(public_id),
+                    Ok(ArticleRef::Legacy(legacy_id)) => public_ids_by_key.get(&legacy_id).copied(),
+                    Err(status) => {
+                        return BatchGetArticleResult {
+                            article_id,
+                            result: Some(batch_get_article_result::Result::InvalidId(
+                                status.message().to_string(),
+                            )),
+                        }
+                    }
+                };
+                let result = match public_id.and_then(|public_id| articles.get(&public_id)) {
+                    Some(article) => {
+                        batch_get_article_result::Result::Article(Box::new(article.clone()))
+                    }
+                    None => batch_get_article_result::Result::NotFound(true),
+                };
+
+                BatchGetArticleResult {
+                    article_id,
+                    result: Some(result),
+                }
+            })
+            .collect();
+
+        Ok(Response::new(BatchGetArticlesResponse {
+            results,
+        }))
+    }
+
+    async fn get_related_articles(
+        &self,
+        request: Request<GetRelatedArticlesRequest>,
+    ) -> Result<Response<GetRelatedArticlesResponse>, Status> {
+        let viewer_id = self.viewer_id(&request);
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, 0)?;
+        let limit = resolve_page_size(
+            req.limit.into(),
+            DEFAULT_RELATED_ARTICLES,
+            MAX_RELATED_ARTICLES,
+        );
+
+        // The article itself has to be visible to the caller.
+        get_article(
+            &self.db_pool,
+            article_id,
+            viewer_id,
+            self.is_moderator(viewer_id)?,
+        )
+        .map_err(|_| Status::failed_precondition("Article not found"))?;
+
+        let now = Utc::now().naive_utc();
+        let related_ids = match self.related_cache.get(article_id, now) {
+            Some(related_ids) => related_ids,
+            None => {
+                let related_ids =
+                    get_related_article_ids(&self.db_pool, article_id, MAX_RELATED_ARTICLES)
+                        .map_err(|_| {
+                            Status::failed_precondition("Getting related articles error")
+                        })?;
+                self.related_cache
+                    .insert(article_id, related_ids.clone(), now);
+                related_ids
+            }
+        };
+
+        // Articles trashed, hidden or unpublished since they were ranked are left out here.
+        let mut entries =
+            get_articles_by_ids(&self.db_pool, &[], &related_ids, viewer_id, false)
+                .map_err(|_| Status::failed_precondition("Getting related articles error"))?;
+        entries.sort_by_key(|entry| related_ids.iter().position(|id| *id == entry.id));
+
+        let representations = req.representations().collect::<Vec<_>>();
+        let articles = entries
+            .into_iter()
+            .take(limit as usize)
+            .map(|entry| {
+                let mut article = into_article(entry, self.settings.app.accept_legacy_ids);
+                retain_representations(&mut article, &representations);
+                article
+            })
+            .collect();
+
+        Ok(Response::new(GetRelatedArticlesResponse {
+            articles,
+        }))
+    }
+
+    async fn get_article_by_slug(
+        &self,
+        request: Request<GetArticleBySlugRequest>,
+    ) -> Result<Response<GetArticleBySlugResponse>, Status> {
+        let viewer_id = self.viewer_id(&request);
+        let viewer_key = viewer_key(&request, viewer_id, &self.trusted_proxies);
+        let req = request.into_inner();
+
+        let (article, redirected) = get_article_by_slug(
+            &self.db_pool,
+            &req.slug,
+            viewer_id,
+            self.is_moderator(viewer_id)?,
+        )
+        .map_err(|_| Status::failed_precondition("Article not found"))?;
+
+        self.record_view(&article, viewer_id, viewer_key);
+
+        let mut article = into_article(article, self.settings.app.accept_legacy_ids);
+        retain_representations(
+            &mut article,
+            &req.representations().collect::<Vec<_>>(),
+        );
+
+        Ok(Response::new(GetArticleBySlugResponse {
+            article: Some(article),
+            redirected,
         }))
     }
 
@@ -28,14 +728,86 @@ impl NewsService for AppState {
         &self,
         request: Request<GetArticlesRequest>,
     ) -> Result<Response<GetArticlesResponse>, Status> {
+        let viewer_id = self.viewer_id(&request);
         let req = request.into_inner();
-        let timestamp = parse_timestamp(&req.last_timestamp);
+        let (order, scope) = resolve_article_order(req.sort(), req.top_window());
+        let view = req.view();
+        let page_size = resolve_page_size(
+            req.page_size,
+            DEFAULT_ARTICLES_PAGE_SIZE,
+            MAX_ARTICLES_PAGE_SIZE,
+        );
+        let filter = ArticleFilter {
+            match_all_tags: req.tag_match() == TagMatch::All,
+            author_id: parse_optional_uuid(&req.author_id, "Invalid author_id")?,
+            author_username: Some(req.author_username).filter(|username| !username.is_empty()),
+            section_id: parse_optional_uuid(&req.section_id, "Invalid section_id")?,
+            section_path: Some(normalize_section_path(&req.section_path))
+                .filter(|path| !path.is_empty()),
+            created_after: parse_optional_timestamp(&req.created_after, "Invalid created_after")?,
+            created_before: parse_optional_timestamp(
+                &req.created_before,
+                "Invalid created_before",
+            )?,
+            language: parse_optional_language(&req.language)?,
+            translation_group_id: parse_optional_uuid(
+                &req.translation_group_id,
+                "Invalid translation_group_id",
+            )?,
+            query: Some(req.query.trim().to_string()).filter(|query| !query.is_empty()),
+            include_hidden: self.is_moderator(viewer_id)?,
+            tags: req.tags,
+        };
+        let page_tokens = self.page_tokens(scope, &format!("{filter:?}"));
+        let cursor = match (req.last_timestamp.is_empty(), order) {
+            (true, _) => page_tokens.decode(&req.page_token)?,
+            // Everything published before the timestamp, as the deprecated field used to page.
+            (false, ArticleOrder::Newest) => Some(PageCursor {
+                direction: PageDirection::Next,
+                position: (
+                    SortKey::Timestamp(
+                        parse_timestamp(&req.last_timestamp)
+                            .ok_or_else(|| Status::invalid_argument("Invalid last_timestamp"))?,
+                    ),
+                    0,
+                ),
+            }),
+            (false, _) => {
+                return Err(Status::invalid_argument(
+                    "last_timestamp pages the newest articles only, use page_token",
+                ))
+            }
+        };
+
+        let articles = get_articles_page(
+            &self.db_pool,
+            order,
+            cursor.as_ref(),
+            page_limit(page_size),
+            filter,
+            viewer_id,
+        )
+        .map_err(|_| Status::failed_precondition("Getting page error"))?;
 
-        let article_page = get_articles_page(&self.db_pool, timestamp, req.page_size)
-            .map_err(|_| Status::failed_precondition("Getting page error"))?;
+        let page = page_tokens.page(articles, page_size, cursor.as_ref(), |entry| {
+            let sort_key = match order {
+                ArticleOrder::Newest => {
+                    SortKey::Timestamp(entry.article.publish_at.unwrap_or(entry.article.created_at))
+                }
+                ArticleOrder::Top { .. } => SortKey::Count(entry.like_count),
+                ArticleOrder::Trending => SortKey::Score(entry.trending_score),
+            };
+
+            (sort_key, entry.article.id)
+        });
 
         Ok(Response::new(GetArticlesResponse {
-            articles: into_articles(article_page),
+            articles: self.load_article_views(
+                page.items.into_iter().map(|entry| entry.article).collect(),
+                view,
+            )?,
+            next_page_token: page.next_page_token,
+            prev_page_token: page.prev_page_token,
         }))
     }
 
@@ -46,17 +818,60 @@ impl NewsService for AppState {
         let user_id = get_user_id(&request)?;
         let req = request.into_inner();
 
+        let (status, publish_at) = match req.status() {
+            ArticleStatus::Unspecified | ArticleStatus::Draft => (ArticleStatus::Draft, None),
+            ArticleStatus::Scheduled if req.publish_at.is_empty() => {
+                return Err(Status::invalid_argument(
+                    "Scheduled article requires publish_at",
+                ))
+            }
+            ArticleStatus::Published | ArticleStatus::Scheduled => {
+                let (status, publish_at) = resolve_publication(&req.publish_at)?;
+                (status, Some(publish_at))
+            }
+            ArticleStatus::Archived => {
+                return Err(Status::invalid_argument(
+                    "Article cannot be created archived",
+                ))
+            }
+        };
+
+        let content_format = req.content_format();
+        let cover_media_id = parse_media_id(&req.cover_media_id)?;
+        let section_id = parse_optional_uuid(&req.section_id, "Invalid section_id")?;
+        let language = parse_optional_language(&req.language)?;
+        let translation_of = match req.translation_of.is_empty() {
+            true => None,
+            false => Some(self.resolve_article_id(&req.translation_of, 0)?),
+        };
+        let content_filter = self.content_filter.rules();
+        let tagged = !req.tags.is_empty();
         let article_id = create_article(
             &self.db_pool,
             user_id.value,
-            req.title.as_str(),
-            req.content.as_str(),
-            req.tags,
+            NewArticle {
+                title: req.title.as_str(),
+                content: req.content.as_str(),
+                content_format: from_content_format(content_format),
+                tags: req.tags,
+                status: from_article_status(status),
+                publish_at,
+                cover_media_id,
+                section_id,
+                language: language.as_deref().unwrap_or(DEFAULT_LANGUAGE),
+                translation_of,
+                content_filter: &content_filter,
+            },
         )
-        .map_err(|_| Status::failed_precondition("Creating article failed"))?;
+        .map_err(|err| section_error_status(err, "Creating article failed"))?;
+
+        if tagged {
+            self.related_cache.clear();
+        }
 
         Ok(Response::new(CreatedArticleResponse {
-            article_id: article_id.id,
+            article_id: article_id.public_id.to_string(),
+            legacy_article_id: into_legacy_id(article_id.id, self.settings.app.accept_legacy_ids),
         }))
     }
 
@@ -66,11 +881,56 @@ impl NewsService for AppState {
     ) -> Result<Response<DeleteArticleResponse>, Status> {
         let user_id = get_user_id(&request)?;
         let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, req.legacy_article_id)?;
+
+        let deleted = delete_article(
+            &self.db_pool,
+            user_id.value,
+            article_id,
+            req.expected_version,
+        )
+        .map_err(|err| article_error_status(err, "Deleting article failed"))?;
+
+        Ok(Response::new(DeleteArticleResponse {
+            article: Some(into_article(
+                deleted,
+                self.settings.app.accept_legacy_ids,
+            )),
+        }))
+    }
+
+    async fn list_deleted_articles(
+        &self,
+        request: Request<ListDeletedArticlesRequest>,
+    ) -> Result<Response<ListDeletedArticlesResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let view = request.get_ref().view();
+
+        let articles = get_deleted_articles(&self.db_pool, user_id.value)
+            .map_err(|_| Status::failed_precondition("Getting deleted articles error"))?;
+
+        Ok(Response::new(ListDeletedArticlesResponse {
+            articles: self.load_article_views(articles, view)?,
+        }))
+    }
+
+    async fn restore_article(
+        &self,
+        request: Request<RestoreArticleRequest>,
+    ) -> Result<Response<RestoreArticleResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, req.legacy_article_id)?;
 
-        delete_article(&self.db_pool, user_id.value, req.article_id)
-            .map_err(|_| Status::failed_precondition("Deleting article failed"))?;
+        let restored = restore_article(&self.db_pool, user_id.value, article_id)
+            .map_err(|err| article_error_status(err, "Restoring article failed"))?;
 
-        Ok(Response::new(DeleteArticleResponse {}))
+        Ok(Response::new(RestoreArticleResponse {
+            article: Some(into_article(
+                restored,
+                self.settings.app.accept_legacy_ids,
+            )),
+        }))
     }
 
     async fn update_article(
@@ -79,17 +939,1088 @@ impl NewsService for AppState {
     ) -> Result<Response<UpdateArticleResponse>, Status> {
         let user_id = get_user_id(&request)?;
         let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, req.legacy_article_id)?;
+
+        // Clients unaware of formats leave it unspecified, their full updates keep the format.
+        let content_format = match req.content_format() {
+            ContentFormat::Unspecified => None,
+            content_format => Some(from_content_format(content_format).to_string()),
+        };
+        let changes = resolve_article_changes(
+            req.update_mask,
+            ArticleChanges {
+                title: Some(req.title),
+                content: Some(req.content),
+                content_format,
+                tags: Some(req.tags),
+                cover_media_id: parse_media_id(&req.cover_media_id)?.map(Some),
+                section_id: parse_optional_uuid(&req.section_id, "Invalid section_id")?,
+                language: parse_optional_language(&req.language)?,
+            },
+        )?;
+        let (updated, tags_changed) = update_article(
+            &self.db_pool,
+            user_id.value,
+            article_id,
+            req.expected_version,
+            changes,
+            &self.content_filter.rules(),
+        )
+        .map_err(|err| section_error_status(err, "Updating article failed"))?;
+
+        if tags_changed {
+            self.related_cache.clear();
+        }
+
+        Ok(Response::new(UpdateArticleResponse {
+            revision: updated.version,
+            version: updated.version,
+            article: Some(into_article(
+                updated,
+                self.settings.app.accept_legacy_ids,
+            )),
+        }))
+    }
+
+    async fn publish_article(
+        &self,
+        request: Request<PublishArticleRequest>,
+    ) -> Result<Response<PublishArticleResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, req.legacy_article_id)?;
+
+        let (status, publish_at) = resolve_publication(&req.publish_at)?;
+
+        let publication = publish_article(
+            &self.db_pool,
+            user_id.value,
+            article_id,
+            from_article_status(status),
+            publish_at,
+        )
+        .map_err(|_| Status::failed_precondition("Publishing article failed"))?;
+
+        if publication.tagged {
+            self.related_cache.clear();
+        }
+
+        Ok(Response::new(PublishArticleResponse {
+            status: into_article_status(&publication.status).into(),
+            publish_at: into_optional_timestamp(publication.publish_at),
+        }))
+    }
+
+    async fn unpublish_article(
+        &self,
+        request: Request<UnpublishArticleRequest>,
+    ) -> Result<Response<UnpublishArticleResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, req.legacy_article_id)?;
+
+        let status = match req.archive {
+            true => ArticleStatus::Archived,
+            false => ArticleStatus::Draft,
+        };
+
+        let publication = unpublish_article(
+            &self.db_pool,
+            user_id.value,
+            article_id,
+            from_article_status(status),
+        )
+        .map_err(|_| Status::failed_precondition("Unpublishing article failed"))?;
+
+        Ok(Response::new(UnpublishArticleResponse {
+            status: into_article_status(&publication.status).into(),
+        }))
+    }
+
+    async fn list_article_revisions(
+        &self,
+        request: Request<ListArticleRevisionsRequest>,
+    ) -> Result<Response<ListArticleRevisionsResponse>, Status> {
+        let viewer_id = self.viewer_id(&request);
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, req.legacy_article_id)?;
+
+        let include_hidden = self.is_moderator(viewer_id)?;
+        let revisions = get_article_revisions(
+            &self.db_pool,
+            article_id,
+            viewer_id,
+            include_hidden,
+        )
+        .map_err(|_| Status::failed_precondition("Getting revisions error"))?;
+
+        Ok(Response::new(ListArticleRevisionsResponse {
+            revisions: into_article_revisions(revisions, self.settings.app.accept_legacy_ids),
+        }))
+    }
+
+    async fn get_article_revision(
+        &self,
+        request: Request<GetArticleRevisionRequest>,
+    ) -> Result<Response<GetArticleRevisionResponse>, Status> {
+        let viewer_id = self.viewer_id(&request);
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, req.legacy_article_id)?;
+
+        let include_hidden = self.is_moderator(viewer_id)?;
+        let revision = get_article_revision(
+            &self.db_pool,
+            article_id,
+            req.revision,
+            viewer_id,
+            include_hidden,
+        )
+        .map_err(|_| Status::failed_precondition("Revision not found"))?;
+
+        Ok(Response::new(GetArticleRevisionResponse {
+            revision: Some(into_article_revision(
+                revision,
+                self.settings.app.accept_legacy_ids,
+            )),
+        }))
+    }
+
+    async fn diff_article_revisions(
+        &self,
+        request: Request<DiffArticleRevisionsRequest>,
+    ) -> Result<Response<DiffArticleRevisionsResponse>, Status> {
+        let viewer_id = self.viewer_id(&request);
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, req.legacy_article_id)?;
+        let granularity = req.granularity();
+        let include_hidden = self.is_moderator(viewer_id)?;
+
+        let from = get_article_revision(
+            &self.db_pool,
+            article_id,
+            req.from_revision,
+            viewer_id,
+            include_hidden,
+        )
+        .map_err(|_| Status::failed_precondition("Revision not found"))?;
+        let to = get_article_revision(
+            &self.db_pool,
+            article_id,
+            req.to_revision,
+            viewer_id,
+            include_hidden,
+        )
+        .map_err(|_| Status::failed_precondition("Revision not found"))?;
+
+        let (added_tags, removed_tags) = diff_tags(&from.tags, &to.tags);
+
+        Ok(Response::new(DiffArticleRevisionsResponse {
+            title: diff_text(&from.title, &to.title, granularity),
+            content: diff_text(&from.content, &to.content, granularity),
+            added_tags,
+            removed_tags,
+        }))
+    }
+
+    async fn get_article_stats(
+        &self,
+        request: Request<GetArticleStatsRequest>,
+    ) -> Result<Response<GetArticleStatsResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, 0)?;
+
+        let (interval, step, default_range) = match req.interval() {
+            StatsInterval::Hour => ("hour", TimeDelta::hours(1), TimeDelta::hours(48)),
+            StatsInterval::Unspecified | StatsInterval::Day => {
+                ("day", TimeDelta::days(1), TimeDelta::days(30))
+            }
+        };
+
+        let end = parse_optional_timestamp(&req.end_time, "Invalid end_time")?
+            .unwrap_or_else(|| Utc::now().naive_utc());
+        let start = parse_optional_timestamp(&req.start_time, "Invalid start_time")?
+            .unwrap_or(end - default_range);
+
+        if start > end {
+            return Err(Status::invalid_argument(
+                "start_time must not be after end_time",
+            ));
+        }
+        if (end - start).num_seconds() / step.num_seconds() >= MAX_STATS_BUCKETS {
+            return Err(Status::invalid_argument(format!(
+                "At most {MAX_STATS_BUCKETS} buckets per request"
+            )));
+        }
 
-        update_article(
+        let (totals, buckets) = get_article_stats(
             &self.db_pool,
             user_id.value,
-            req.article_id,
-            &req.title,
-            &req.content,
-            req.tags,
+            article_id,
+            interval,
+            start,
+            end,
         )
-        .map_err(|_| Status::failed_precondition("Updating article failed"))?;
+        .map_err(|err| article_error_status(err, "Getting article stats failed"))?;
+
+        Ok(Response::new(GetArticleStatsResponse {
+            view_count: totals.view_count,
+            like_count: totals.like_count,
+            comment_count: totals.comment_count,
+            buckets: buckets.into_iter().map(into_article_stats_bucket).collect(),
+        }))
+    }
+
+    async fn invite_contributor(
+        &self,
+        request: Request<InviteContributorRequest>,
+    ) -> Result<Response<InviteContributorResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, 0)?;
+        let invitee_id = parse_user_id(&req.user_id)?;
+
+        let role = req.role();
+        if role == ContributorRole::Author {
+            return Err(Status::invalid_argument(
+                "Author role goes with the ownership, transfer it instead",
+            ));
+        }
 
-        Ok(Response::new(UpdateArticleResponse {}))
+        let contributor = invite_contributor(
+            &self.db_pool,
+            user_id.value,
+            article_id,
+            invitee_id,
+            from_contributor_role(role),
+        )
+        .map_err(|err| contributor_error_status(err, "Inviting contributor failed"))?;
+
+        Ok(Response::new(InviteContributorResponse {
+            contributor: Some(into_contributor(contributor)),
+        }))
+    }
+
+    async fn list_contributor_invitations(
+        &self,
+        request: Request<ListContributorInvitationsRequest>,
+    ) -> Result<Response<ListContributorInvitationsResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+
+        let invitations = get_contributor_invitations(&self.db_pool, user_id.value)
+            .map_err(|_| Status::failed_precondition("Getting invitations error"))?;
+
+        Ok(Response::new(
+            ListContributorInvitationsResponse {
+                invitations: invitations
+                    .into_iter()
+                    .map(into_contributor_invitation)
+                    .collect(),
+            },
+        ))
+    }
+
+    async fn accept_contributor_invitation(
+        &self,
+        request: Request<AcceptContributorInvitationRequest>,
+    ) -> Result<Response<AcceptContributorInvitationResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, 0)?;
+
+        let contributor =
+            accept_contributor_invitation(&self.db_pool, user_id.value, article_id)
+                .map_err(|err| contributor_error_status(err, "Accepting invitation failed"))?;
+
+        Ok(Response::new(
+            AcceptContributorInvitationResponse {
+                contributor: Some(into_contributor(contributor)),
+            },
+        ))
+    }
+
+    async fn decline_contributor_invitation(
+        &self,
+        request: Request<DeclineContributorInvitationRequest>,
+    ) -> Result<Response<DeclineContributorInvitationResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, 0)?;
+
+        decline_contributor_invitation(&self.db_pool, user_id.value, article_id)
+            .map_err(|err| contributor_error_status(err, "Declining invitation failed"))?;
+
+        Ok(Response::new(
+            DeclineContributorInvitationResponse {},
+        ))
+    }
+
+    async fn list_contributors(
+        &self,
+        request: Request<ListContributorsRequest>,
+    ) -> Result<Response<ListContributorsResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, 0)?;
+
+        let include_hidden = self.is_moderator(Some(user_id.value))?;
+        let contributors = get_contributors(
+            &self.db_pool,
+            user_id.value,
+            article_id,
+            include_hidden,
+        )
+        .map_err(|err| contributor_error_status(err, "Getting contributors error"))?;
+
+        Ok(Response::new(ListContributorsResponse {
+            contributors: into_contributors(contributors),
+        }))
+    }
+
+    async fn remove_contributor(
+        &self,
+        request: Request<RemoveContributorRequest>,
+    ) -> Result<Response<RemoveContributorResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, 0)?;
+        let contributor_id = parse_user_id(&req.user_id)?;
+
+        remove_contributor(
+            &self.db_pool,
+            user_id.value,
+            article_id,
+            contributor_id,
+        )
+        .map_err(|err| contributor_error_status(err, "Removing contributor failed"))?;
+
+        Ok(Response::new(RemoveContributorResponse {}))
+    }
+
+    async fn reorder_contributors(
+        &self,
+        request: Request<ReorderContributorsRequest>,
+    ) -> Result<Response<ReorderContributorsResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, 0)?;
+        let contributor_ids = req
+            .user_ids
+            .iter()
+            .map(|contributor_id| parse_user_id(contributor_id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let byline = reorder_contributors(
+            &self.db_pool,
+            user_id.value,
+            article_id,
+            &contributor_ids,
+        )
+        .map_err(|err| contributor_error_status(err, "Reordering contributors failed"))?;
+
+        Ok(Response::new(ReorderContributorsResponse {
+            byline: into_contributors(byline),
+        }))
+    }
+
+    async fn transfer_article_ownership(
+        &self,
+        request: Request<TransferArticleOwnershipRequest>,
+    ) -> Result<Response<TransferArticleOwnershipResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, 0)?;
+        let new_author_id = parse_user_id(&req.user_id)?;
+
+        let article = transfer_article_ownership(
+            &self.db_pool,
+            user_id.value,
+            article_id,
+            new_author_id,
+        )
+        .map_err(|err| contributor_error_status(err, "Transferring ownership failed"))?;
+
+        Ok(Response::new(TransferArticleOwnershipResponse {
+            article: Some(into_article(
+                article,
+                self.settings.app.accept_legacy_ids,
+            )),
+        }))
+    }
+
+    async fn add_bookmark(
+        &self,
+        request: Request<AddBookmarkRequest>,
+    ) -> Result<Response<AddBookmarkResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, 0)?;
+        let reading_list_id = parse_optional_uuid(&req.reading_list_id, "Invalid reading_list_id")?;
+
+        let include_hidden = self.is_moderator(Some(user_id.value))?;
+
+        let bookmark = add_bookmark(
+            &self.db_pool,
+            user_id.value,
+            article_id,
+            reading_list_id,
+            include_hidden,
+        )
+        .map_err(|err| bookmark_error_status(err, "Adding bookmark failed"))?;
+
+        Ok(Response::new(AddBookmarkResponse {
+            bookmark: Some(into_bookmark(bookmark, None)),
+        }))
+    }
+
+    async fn remove_bookmark(
+        &self,
+        request: Request<RemoveBookmarkRequest>,
+    ) -> Result<Response<RemoveBookmarkResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, 0)?;
+
+        remove_bookmark(&self.db_pool, user_id.value, article_id)
+            .map_err(|err| bookmark_error_status(err, "Removing bookmark failed"))?;
+
+        Ok(Response::new(RemoveBookmarkResponse {}))
+    }
+
+    async fn list_bookmarks(
+        &self,
+        request: Request<ListBookmarksRequest>,
+    ) -> Result<Response<ListBookmarksResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let view = req.view();
+        let reading_list_id = parse_optional_uuid(&req.reading_list_id, "Invalid reading_list_id")?;
+        let page_tokens = self.page_tokens(
+            BOOKMARKS_PAGE_SCOPE,
+            &format!("{reading_list_id:?}"),
+        );
+        let page_size = resolve_page_size(
+            req.page_size,
+            DEFAULT_ARTICLES_PAGE_SIZE,
+            MAX_ARTICLES_PAGE_SIZE,
+        );
+        let cursor = page_tokens.decode(&req.page_token)?;
+
+        let bookmarks = get_bookmarks_page(
+            &self.db_pool,
+            user_id.value,
+            reading_list_id,
+            cursor.as_ref(),
+            page_limit(page_size),
+            self.is_moderator(Some(user_id.value))?,
+        )
+        .map_err(|err| bookmark_error_status(err, "Getting bookmarks error"))?;
+
+        let page = page_tokens.page(bookmarks, page_size, cursor.as_ref(), |entry| {
+            (entry.bookmark.bookmarked_at, entry.bookmark_id)
+        });
+
+        let (summaries, bookmarks): (Vec<_>, Vec<_>) = page
+            .items
+            .into_iter()
+            .map(|entry| (entry.article, entry.bookmark))
+            .unzip();
+        let articles = self.load_article_views(summaries, view)?;
+
+        Ok(Response::new(ListBookmarksResponse {
+            bookmarks: bookmarks
+                .into_iter()
+                .zip(articles)
+                .map(|(bookmark, article)| into_bookmark(bookmark, Some(article)))
+                .collect(),
+            next_page_token: page.next_page_token,
+            prev_page_token: page.prev_page_token,
+        }))
+    }
+
+    async fn create_reading_list(
+        &self,
+        request: Request<CreateReadingListRequest>,
+    ) -> Result<Response<CreateReadingListResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let name = req.name.trim();
+
+        if name.is_empty() {
+            return Err(Status::invalid_argument(
+                "Missing reading list name",
+            ));
+        }
+        if name.chars().count() > MAX_READING_LIST_NAME_LENGTH {
+            return Err(Status::invalid_argument(format!(
+                "Reading list name is longer than {MAX_READING_LIST_NAME_LENGTH} characters"
+            )));
+        }
+
+        let reading_list = create_reading_list(&self.db_pool, user_id.value, name)
+            .map_err(|err| bookmark_error_status(err, "Creating reading list failed"))?;
+
+        Ok(Response::new(CreateReadingListResponse {
+            reading_list: Some(into_reading_list(reading_list)),
+        }))
+    }
+
+    async fn list_reading_lists(
+        &self,
+        request: Request<ListReadingListsRequest>,
+    ) -> Result<Response<ListReadingListsResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+
+        let reading_lists = get_reading_lists(&self.db_pool, user_id.value)
+            .map_err(|_| Status::failed_precondition("Getting reading lists error"))?;
+
+        Ok(Response::new(ListReadingListsResponse {
+            reading_lists: into_reading_lists(reading_lists),
+        }))
+    }
+
+    async fn reorder_reading_lists(
+        &self,
+        request: Request<ReorderReadingListsRequest>,
+    ) -> Result<Response<ReorderReadingListsResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let reading_list_ids = req
+            .reading_list_ids
+            .iter()
+            .map(|reading_list_id| parse_reading_list_id(reading_list_id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let reading_lists = reorder_reading_lists(&self.db_pool, user_id.value, &reading_list_ids)
+            .map_err(|err| bookmark_error_status(err, "Reordering reading lists failed"))?;
+
+        Ok(Response::new(ReorderReadingListsResponse {
+            reading_lists: into_reading_lists(reading_lists),
+        }))
+    }
+
+    async fn delete_reading_list(
+        &self,
+        request: Request<DeleteReadingListRequest>,
+    ) -> Result<Response<DeleteReadingListResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let reading_list_id = parse_reading_list_id(&req.reading_list_id)?;
+
+        delete_reading_list(&self.db_pool, user_id.value, reading_list_id)
+            .map_err(|err| bookmark_error_status(err, "Deleting reading list failed"))?;
+
+        Ok(Response::new(DeleteReadingListResponse {}))
+    }
+
+    async fn report_article(
+        &self,
+        request: Request<ReportArticleRequest>,
+    ) -> Result<Response<ReportArticleResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, 0)?;
+        let details = parse_report_details(&req.details)?;
+
+        let report = report_article(
+            &self.db_pool,
+            user_id.value,
+            article_id,
+            from_report_reason(req.reason()),
+            details,
+        )
+        .map_err(|err| moderation_error_status(err, "Reporting article failed"))?;
+
+        Ok(Response::new(ReportArticleResponse {
+            report: Some(into_report(report)),
+        }))
+    }
+
+    async fn report_comment(
+        &self,
+        request: Request<ReportCommentRequest>,
+    ) -> Result<Response<ReportCommentResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let comment_id = parse_comment_id(&req.comment_id)?;
+        let details = parse_report_details(&req.details)?;
+
+        let report = report_comment(
+            &self.db_pool,
+            user_id.value,
+            comment_id,
+            from_report_reason(req.reason()),
+            details,
+        )
+        .map_err(|err| moderation_error_status(err, "Reporting comment failed"))?;
+
+        Ok(Response::new(ReportCommentResponse {
+            report: Some(into_report(report)),
+        }))
+    }
+
+    async fn list_moderation_queue(
+        &self,
+        request: Request<ListModerationQueueRequest>,
+    ) -> Result<Response<ListModerationQueueResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        self.require_role(user_id.value, MODERATOR_ROLE)?;
+        let req = request.into_inner();
+        let page_tokens = self.page_tokens(REPORTS_PAGE_SCOPE, req.status().as_str_name());
+        let page_size = resolve_page_size(
+            req.page_size,
+            DEFAULT_ARTICLES_PAGE_SIZE,
+            MAX_ARTICLES_PAGE_SIZE,
+        );
+        let cursor = page_tokens.decode(&req.page_token)?;
+
+        let reports = get_reports_page(
+            &self.db_pool,
+            from_report_status(req.status()),
+            cursor.as_ref(),
+            page_limit(page_size),
+        )
+        .map_err(|_| Status::failed_precondition("Getting moderation queue error"))?;
+
+        let page = page_tokens.page(reports, page_size, cursor.as_ref(), |entry| {
+            (entry.created_at, entry.id)
+        });
+
+        Ok(Response::new(ListModerationQueueResponse {
+            reports: into_reports(page.items),
+            next_page_token: page.next_page_token,
+            prev_page_token: page.prev_page_token,
+        }))
+    }
+
+    async fn moderate_report(
+        &self,
+        request: Request<ModerateReportRequest>,
+    ) -> Result<Response<ModerateReportResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        self.require_role(user_id.value, MODERATOR_ROLE)?;
+        let req = request.into_inner();
+        let report_id = Uuid::parse_str(&req.report_id).map_err(|_| {
+            Status::invalid_argument(format!("Invalid report_id: {}", req.report_id))
+        })?;
+        let action = from_moderation_action(req.action())
+            .ok_or_else(|| Status::invalid_argument("Missing moderation action"))?;
+        let reason = req.reason.trim();
+
+        if reason.is_empty() {
+            return Err(Status::invalid_argument(
+                "Missing moderation reason",
+            ));
+        }
+        if reason.chars().count() > MAX_MODERATION_REASON_LENGTH {
+            return Err(Status::invalid_argument(format!(
+                "Moderation reason is longer than {MAX_MODERATION_REASON_LENGTH} characters"
+            )));
+        }
+
+        let report = moderate_report(
+            &self.db_pool,
+            user_id.value,
+            report_id,
+            action,
+            reason,
+        )
+        .map_err(|err| moderation_error_status(err, "Moderating report failed"))?;
+
+        Ok(Response::new(ModerateReportResponse {
+            report: Some(into_report(report)),
+        }))
+    }
+
+    async fn restore_article_revision(
+        &self,
+        request: Request<RestoreArticleRevisionRequest>,
+    ) -> Result<Response<RestoreArticleRevisionResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let req = request.into_inner();
+        let article_id = self.resolve_article_id(&req.article_id, req.legacy_article_id)?;
+
+        let revision = get_article_revision(
+            &self.db_pool,
+            article_id,
+            req.revision,
+            Some(user_id.value),
+            self.is_moderator(Some(user_id.value))?,
+        )
+        .map_err(|_| Status::failed_precondition("Revision not found"))?;
+
+        let (restored, tags_changed) = update_article(
+            &self.db_pool,
+            user_id.value,
+            article_id,
+            req.expected_version,
+            ArticleChanges {
+                title: Some(revision.title),
+                content: Some(revision.content),
+                content_format: Some(revision.content_format),
+                tags: Some(revision.tags),
+                cover_media_id: None,
+                section_id: None,
+                language: None,
+            },
+            &self.content_filter.rules(),
+        )
+        .map_err(|err| article_error_status(err, "Restoring revision failed"))?;
+
+        if tags_changed {
+            self.related_cache.clear();
+        }
+
+        Ok(Response::new(RestoreArticleRevisionResponse {
+            revision: restored.version,
+            version: restored.version,
+            article: Some(into_article(
+                restored,
+                self.settings.app.accept_legacy_ids,
+            )),
+        }))
+    }
+
+    async fn upload_media(
+        &self,
+        request: Request<Streaming<UploadMediaRequest>>,
+    ) -> Result<Response<UploadMediaResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        let mut stream = request.into_inner();
+        let media_settings = &self.settings.media;
+
+        let mut file_name = String::new();
+        let mut data = Vec::new();
+
+        while let Some(message) = stream.message().await? {
+            match message.payload {
+                Some(upload_media_request::Payload::Metadata(metadata)) => {
+                    file_name = metadata.file_name
+                }
+                Some(upload_media_request::Payload::Chunk(chunk)) => {
+                    if data.len() + chunk.len() > media_settings.max_upload_bytes {
+                        return Err(Status::invalid_argument(format!(
+                            "Media exceeds {} bytes",
+                            media_settings.max_upload_bytes
+                        )));
+                    }
+                    data.extend_from_slice(&chunk);
+                }
+                None => {}
+            }
+        }
+
+        if file_name.chars().count() > MAX_FILE_NAME_LENGTH {
+            return Err(Status::invalid_argument("File name is too long"));
+        }
+
+        let content_type = sniff_content_type(&data)
+            .ok_or_else(|| Status::invalid_argument("Unsupported media type"))?;
+
+        let thumbnail_size = media_settings.thumbnail_size;
+        let (data, image) = spawn_blocking(move || {
+            let image = process_image(&data, content_type, thumbnail_size);
+            (data, image)
+        })
+        .await
+        .map_err(|_| Status::internal("Processing media failed"))?;
+        let image = image.map_err(|_| Status::invalid_argument("Media cannot be decoded"))?;
+
+        let public_id = Uuid::now_v7();
+        let storage_key = format!("originals/{public_id}");
+        let thumbnail_key = format!("thumbnails/{public_id}");
+
+        self.media_storage
+            .put(&storage_key, content_type, &data)
+            .await
+            .map_err(|_| Status::failed_precondition("Storing media failed"))?;
+        self.media_storage
+            .put(&thumbnail_key, content_type, &image.thumbnail)
+            .await
+            .map_err(|_| Status::failed_precondition("Storing media failed"))?;
+
+        let media = create_media(
+            &self.db_pool,
+            user_id.value,
+            NewMedia {
+                public_id,
+                file_name: &file_name,
+                content_type,
+                size_bytes: data.len() as i64,
+                width: image.width as i32,
+                height: image.height as i32,
+                storage_key: &storage_key,
+                thumbnail_key: &thumbnail_key,
+            },
+        );
+
+        let media = match media {
+            Ok(media) => media,
+            Err(_) => {
+                for key in [&storage_key, &thumbnail_key] {
+                    _ = self.media_storage.delete(key).await;
+                }
+                return Err(Status::failed_precondition("Saving media failed"));
+            }
+        };
+
+        Ok(Response::new(UploadMediaResponse {
+            media: Some(into_media(media)),
+        }))
+    }
+
+    type DownloadMediaStream =
+        Pin<Box<dyn Stream<Item = Result<DownloadMediaResponse, Status>> + Send>>;
+
+    async fn download_media(
+        &self,
+        request: Request<DownloadMediaRequest>,
+    ) -> Result<Response<Self::DownloadMediaStream>, Status> {
+        let viewer_id = self.viewer_id(&request);
+        let include_hidden = self.is_moderator(viewer_id)?;
+        let req = request.into_inner();
+        let media_id = parse_media_id(&req.media_id)?
+            .ok_or_else(|| Status::invalid_argument("Missing media_id"))?;
+
+        // Media the caller may not see is reported as missing, like drafts of others.
+        let media = get_media(&self.db_pool, media_id, viewer_id, include_hidden)
+            .map_err(|_| Status::not_found("Media not found"))?;
+
+        let key = match req.thumbnail {
+            true => &media.thumbnail_key,
+            false => &media.storage_key,
+        };
+        let data = self
+            .media_storage
+            .get(key)
+            .await
+            .map_err(|_| Status::failed_precondition("Reading media failed"))?;
+
+        let mut messages = vec![Ok(DownloadMediaResponse {
+            payload: Some(download_media_response::Payload::Media(
+                into_media(media),
+            )),
+        })];
+        messages.extend(data.chunks(MEDIA_CHUNK_SIZE).map(|chunk| {
+            Ok(DownloadMediaResponse {
+                payload: Some(download_media_response::Payload::Chunk(
+                    chunk.to_vec(),
+                )),
+            })
+        }));
+
+        Ok(Response::new(Box::pin(tokio_stream::iter(
+            messages,
+        ))))
+    }
+
+    async fn list_tags(
+        &self,
+        request: Request<ListTagsRequest>,
+    ) -> Result<Response<ListTagsResponse>, Status> {
+        let req = request.into_inner();
+        let page_size = resolve_page_size(
+            req.page_size.into(),
+            DEFAULT_TAGS_PAGE_SIZE,
+            MAX_TAGS_PAGE_SIZE,
+        );
+        let page_tokens = self.page_tokens(TAGS_PAGE_SCOPE, "");
+        let cursor = page_tokens.decode(&req.page_token)?;
+
+        let tags = get_tags_page(
+            &self.db_pool,
+            cursor.as_ref(),
+            page_limit(page_size),
+        )
+        .map_err(|_| Status::failed_precondition("Getting tags error"))?;
+
+        let page = page_tokens.page(tags, page_size, cursor.as_ref(), |tag| {
+            (tag.article_count, tag.name.clone())
+        });
+
+        Ok(Response::new(ListTagsResponse {
+            tags: into_tags(page.items),
+            next_page_token: page.next_page_token,
+            prev_page_token: page.prev_page_token,
+        }))
+    }
+
+    async fn suggest_tags(
+        &self,
+        request: Request<SuggestTagsRequest>,
+    ) -> Result<Response<SuggestTagsResponse>, Status> {
+        let req = request.into_inner();
+        let limit = resolve_page_size(
+            req.limit.into(),
+            DEFAULT_TAG_SUGGESTIONS,
+            MAX_TAG_SUGGESTIONS,
+        );
+
+        let Some(query) = normalize_tag(&req.query) else {
+            return Ok(Response::new(SuggestTagsResponse {
+                tags: vec![],
+            }));
+        };
+
+        let tags = suggest_tags(&self.db_pool, &query, limit)
+            .map_err(|_| Status::failed_precondition("Suggesting tags error"))?;
+
+        Ok(Response::new(SuggestTagsResponse {
+            tags: into_tags(tags),
+        }))
+    }
+
+    async fn rename_tag(
+        &self,
+        request: Request<RenameTagRequest>,
+    ) -> Result<Response<RenameTagResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        self.require_role(user_id.value, ADMIN_ROLE)?;
+        let req = request.into_inner();
+
+        let name =
+            normalize_tag(&req.name).ok_or_else(|| Status::invalid_argument("Missing tag name"))?;
+        let new_name = normalize_tag(&req.new_name)
+            .ok_or_else(|| Status::invalid_argument("Missing new tag name"))?;
+
+        let tag = rename_tag(&self.db_pool, &name, &new_name)
+            .map_err(|err| tag_error_status(err, "Renaming tag failed"))?;
+
+        self.related_cache.clear();
+
+        Ok(Response::new(RenameTagResponse {
+            tag: Some(into_tag(tag)),
+        }))
+    }
+
+    async fn merge_tags(
+        &self,
+        request: Request<MergeTagsRequest>,
+    ) -> Result<Response<MergeTagsResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        self.require_role(user_id.value, ADMIN_ROLE)?;
+        let req = request.into_inner();
+
+        let target_name = normalize_tag(&req.target_name)
+            .ok_or_else(|| Status::invalid_argument("Missing target tag name"))?;
+
+        let tag = merge_tags(
+            &self.db_pool,
+            normalize_tags(req.source_names),
+            &target_name,
+        )
+        .map_err(|err| tag_error_status(err, "Merging tags failed"))?;
+
+        self.related_cache.clear();
+
+        Ok(Response::new(MergeTagsResponse {
+            tag: Some(into_tag(tag)),
+        }))
+    }
+
+    async fn list_sections(
+        &self,
+        _request: Request<ListSectionsRequest>,
+    ) -> Result<Response<ListSectionsResponse>, Status> {
+        let sections = get_sections(&self.db_pool)
+            .map_err(|_| Status::failed_precondition("Getting sections error"))?;
+
+        Ok(Response::new(ListSectionsResponse {
+            sections: into_sections(sections),
+        }))
+    }
+
+    async fn create_section(
+        &self,
+        request: Request<CreateSectionRequest>,
+    ) -> Result<Response<CreateSectionResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        self.require_role(user_id.value, EDITOR_ROLE)?;
+        let req = request.into_inner();
+
+        let name = parse_section_name(&req.name)?;
+        let slug = match req.slug.is_empty() {
+            true => slugify(name),
+            false => slugify(&req.slug),
+        };
+        let parent_id = parse_optional_uuid(&req.parent_id, "Invalid parent_id")?;
+
+        let section = create_section(
+            &self.db_pool,
+            NewSection {
+                name,
+                slug: &slug,
+                parent_id,
+            },
+        )
+        .map_err(|err| section_error_status(err, "Creating section failed"))?;
+
+        Ok(Response::new(CreateSectionResponse {
+            section: Some(into_section(section)),
+        }))
+    }
+
+    async fn update_section(
+        &self,
+        request: Request<UpdateSectionRequest>,
+    ) -> Result<Response<UpdateSectionResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        self.require_role(user_id.value, EDITOR_ROLE)?;
+        let req = request.into_inner();
+
+        let section_id = parse_section_id(&req.section_id)?;
+        let name = parse_section_name(&req.name)?;
+        let slug = Some(req.slug)
+            .filter(|slug| !slug.is_empty())
+            .map(|slug| slugify(&slug));
+
+        let section = update_section(&self.db_pool, section_id, name, slug.as_deref())
+            .map_err(|err| section_error_status(err, "Updating section failed"))?;
+
+        Ok(Response::new(UpdateSectionResponse {
+            section: Some(into_section(section)),
+        }))
+    }
+
+    async fn move_section(
+        &self,
+        request: Request<MoveSectionRequest>,
+    ) -> Result<Response<MoveSectionResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        self.require_role(user_id.value, EDITOR_ROLE)?;
+        let req = request.into_inner();
+
+        let section_id = parse_section_id(&req.section_id)?;
+        let parent_id = parse_optional_uuid(&req.parent_id, "Invalid parent_id")?;
+
+        let section = move_section(&self.db_pool, section_id, parent_id)
+            .map_err(|err| section_error_status(err, "Moving section failed"))?;
+
+        Ok(Response::new(MoveSectionResponse {
+            section: Some(into_section(section)),
+        }))
+    }
+
+    async fn merge_sections(
+        &self,
+        request: Request<MergeSectionsRequest>,
+    ) -> Result<Response<MergeSectionsResponse>, Status> {
+        let user_id = get_user_id(&request)?;
+        self.require_role(user_id.value, EDITOR_ROLE)?;
+        let req = request.into_inner();
+
+        let source_ids = req
+            .source_ids
+            .iter()
+            .map(|source_id| parse_section_id(source_id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let target_id = parse_section_id(&req.target_id)?;
+
+        let section = merge_sections(&self.db_pool, &source_ids, target_id)
+            .map_err(|err| section_error_status(err, "Merging sections failed"))?;
+
+        Ok(Response::new(MergeSectionsResponse {
+            section: Some(into_section(section)),
+        }))
     }
 }
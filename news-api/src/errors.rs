@@ -0,0 +1,266 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use tonic::Status;
+
+#[derive(Debug)]
+pub enum ArticleError {
+    NotFound,
+    PermissionDenied,
+    VersionMismatch { current_version: i32 },
+    MediaNotFound,
+    TranslationExists,
+    Moderated,
+    Rejected { rule: String },
+}
+
+impl Display for ArticleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArticleError::NotFound => write!(f, "article not found"),
+            ArticleError::PermissionDenied => write!(f, "article belongs to another author"),
+            ArticleError::VersionMismatch { current_version } => {
+                write!(
+                    f,
+                    "article version mismatch, current version is {current_version}"
+                )
+            }
+            ArticleError::MediaNotFound => write!(f, "media not found among your uploads"),
+            ArticleError::TranslationExists => {
+                write!(
+                    f,
+                    "article already has a translation in this language"
+                )
+            }
+            ArticleError::Moderated => write!(f, "article was removed by a moderator"),
+            ArticleError::Rejected { rule } => write!(f, "content rejected by the {rule} rule"),
+        }
+    }
+}
+
+impl Error for ArticleError {}
+
+/// Maps known article errors to their gRPC status, falling back to `FAILED_PRECONDITION`.
+pub fn article_error_status(err: anyhow::Error, message: &str) -> Status {
+    match err.downcast_ref::<ArticleError>() {
+        Some(err @ ArticleError::NotFound) => Status::not_found(err.to_string()),
+        Some(err @ ArticleError::PermissionDenied) => Status::permission_denied(err.to_string()),
+        Some(err @ ArticleError::VersionMismatch { .. }) => Status::aborted(err.to_string()),
+        Some(err @ ArticleError::MediaNotFound) => Status::invalid_argument(err.to_string()),
+        Some(err @ ArticleError::TranslationExists) => Status::already_exists(err.to_string()),
+        Some(err @ ArticleError::Moderated) => Status::failed_precondition(err.to_string()),
+        Some(err @ ArticleError::Rejected { .. }) => Status::invalid_argument(err.to_string()),
+        None => Status::failed_precondition(message),
+    }
+}
+
+#[derive(Debug)]
+pub enum BookmarkError {
+    NotFound,
+    ReadingListNotFound,
+    ReadingListExists,
+    ReadingListOrderMismatch,
+}
+
+impl Display for BookmarkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BookmarkError::NotFound => write!(f, "article is not bookmarked"),
+            BookmarkError::ReadingListNotFound => write!(f, "reading list not found"),
+            BookmarkError::ReadingListExists => {
+                write!(f, "reading list with this name already exists")
+            }
+            BookmarkError::ReadingListOrderMismatch => {
+                write!(
+                    f,
+                    "order must list each of your reading lists once"
+                )
+            }
+        }
+    }
+}
+
+impl Error for BookmarkError {}
+
+/// Maps known bookmark errors to their gRPC status, falling back to [`article_error_status`] for
+/// the bookmarked article.
+pub fn bookmark_error_status(err: anyhow::Error, message: &str) -> Status {
+    match err.downcast_ref::<BookmarkError>() {
+        Some(err @ (BookmarkError::NotFound | BookmarkError::ReadingListNotFound)) => {
+            Status::not_found(err.to_string())
+        }
+        Some(err @ BookmarkError::ReadingListExists) => Status::already_exists(err.to_string()),
+        Some(err @ BookmarkError::ReadingListOrderMismatch) => {
+            Status::invalid_argument(err.to_string())
+        }
+        None => article_error_status(err, message),
+    }
+}
+
+#[derive(Debug)]
+pub enum ContributorError {
+    NotFound,
+    UserNotFound,
+    AlreadyInvited,
+    InvitationNotFound,
+    AuthorRequired,
+    OrderMismatch,
+}
+
+impl Display for ContributorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContributorError::NotFound => write!(f, "contributor not found"),
+            ContributorError::UserNotFound => write!(f, "user not found"),
+            ContributorError::AlreadyInvited => {
+                write!(f, "user is already a contributor or invited")
+            }
+            ContributorError::InvitationNotFound => {
+                write!(f, "no pending invitation to this article")
+            }
+            ContributorError::AuthorRequired => {
+                write!(
+                    f,
+                    "author cannot leave the article, transfer the ownership first"
+                )
+            }
+            ContributorError::OrderMismatch => {
+                write!(
+                    f,
+                    "order must list each contributor on the byline once"
+                )
+            }
+        }
+    }
+}
+
+impl Error for ContributorError {}
+
+/// Maps known contributor errors to their gRPC status, falling back to [`article_error_status`]
+/// for the article contributed to.
+pub fn contributor_error_status(err: anyhow::Error, message: &str) -> Status {
+    match err.downcast_ref::<ContributorError>() {
+        Some(
+            err @ (ContributorError::NotFound
+            | ContributorError::UserNotFound
+            | ContributorError::InvitationNotFound),
+        ) => Status::not_found(err.to_string()),
+        Some(err @ ContributorError::AlreadyInvited) => Status::already_exists(err.to_string()),
+        Some(err @ ContributorError::AuthorRequired) => {
+            Status::failed_precondition(err.to_string())
+        }
+        Some(err @ ContributorError::OrderMismatch) => Status::invalid_argument(err.to_string()),
+        None => article_error_status(err, message),
+    }
+}
+
+#[derive(Debug)]
+pub enum ModerationError {
+    ReportNotFound,
+    CommentNotFound,
+    AlreadyReported,
+    AlreadyModerated,
+}
+
+impl Display for ModerationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModerationError::ReportNotFound => write!(f, "report not found"),
+            ModerationError::CommentNotFound => write!(f, "comment not found"),
+            ModerationError::AlreadyReported => {
+                write!(f, "you have already reported this content")
+            }
+            ModerationError::AlreadyModerated => write!(f, "report was already moderated"),
+        }
+    }
+}
+
+impl Error for ModerationError {}
+
+/// Maps known moderation errors to their gRPC status, falling back to [`article_error_status`]
+/// for the reported article.
+pub fn moderation_error_status(err: anyhow::Error, message: &str) -> Status {
+    match err.downcast_ref::<ModerationError>() {
+        Some(err @ (ModerationError::ReportNotFound | ModerationError::CommentNotFound)) => {
+            Status::not_found(err.to_string())
+        }
+        Some(err @ ModerationError::AlreadyReported) => Status::already_exists(err.to_string()),
+        Some(err @ ModerationError::AlreadyModerated) => {
+            Status::failed_precondition(err.to_string())
+        }
+        None => article_error_status(err, message),
+    }
+}
+
+#[derive(Debug)]
+pub enum SectionError {
+    NotFound,
+    AlreadyExists,
+    InvalidParent,
+    DefaultSection,
+}
+
+impl Display for SectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SectionError::NotFound => write!(f, "section not found"),
+            SectionError::AlreadyExists => {
+                write!(
+                    f,
+                    "section with this slug already exists at this level"
+                )
+            }
+            SectionError::InvalidParent => {
+                write!(
+                    f,
+                    "section cannot be placed within itself or its subsections"
+                )
+            }
+            SectionError::DefaultSection => {
+                write!(
+                    f,
+                    "default section cannot be moved, re-slugged or merged"
+                )
+            }
+        }
+    }
+}
+
+impl Error for SectionError {}
+
+/// Maps known section errors to their gRPC status, falling back to [`article_error_status`] for
+/// the articles filed under them.
+pub fn section_error_status(err: anyhow::Error, message: &str) -> Status {
+    match err.downcast_ref::<SectionError>() {
+        Some(err @ SectionError::NotFound) => Status::not_found(err.to_string()),
+        Some(err @ SectionError::AlreadyExists) => Status::already_exists(err.to_string()),
+        Some(err @ SectionError::InvalidParent) => Status::invalid_argument(err.to_string()),
+        Some(err @ SectionError::DefaultSection) => Status::failed_precondition(err.to_string()),
+        None => article_error_status(err, message),
+    }
+}
+
+#[derive(Debug)]
+pub enum TagError {
+    NotFound,
+    AlreadyExists,
+}
+
+impl Display for TagError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagError::NotFound => write!(f, "tag not found"),
+            TagError::AlreadyExists => write!(f, "tag already exists, merge the tags instead"),
+        }
+    }
+}
+
+impl Error for TagError {}
+
+/// Maps known tag errors to their gRPC status, falling back to `FAILED_PRECONDITION`.
+pub fn tag_error_status(err: anyhow::Error, message: &str) -> Status {
+    match err.downcast_ref::<TagError>() {
+        Some(err @ TagError::NotFound) => Status::not_found(err.to_string()),
+        Some(err @ TagError::AlreadyExists) => Status::already_exists(err.to_string()),
+        None => Status::failed_precondition(message),
+    }
+}
@@ -0,0 +1,145 @@
+use db_schema::models::ArticleSummaryEntry;
+use diesel::internal::derives::multiconnection::chrono::{DateTime, NaiveDateTime, SecondsFormat};
+use std::fmt::Write;
+
+/// Articles of a feed, newest first, with what the feed says about itself.
+pub struct Feed<'a> {
+    pub title: &'a str,
+    /// Public address of the site, without a trailing slash.
+    pub site_url: &'a str,
+    /// Identifies the feed, the path it is served at.
+    pub path: &'a str,
+    pub articles: &'a [ArticleSummaryEntry],
+    /// Last change to any article matching the feed, including the ones it no longer lists, so
+    /// removals move it forward too. `None` when nothing ever matched.
+    pub last_modified: Option<NaiveDateTime>,
+}
+
+impl Feed<'_> {
+    fn article_url(&self, article: &ArticleSummaryEntry) -> String {
+        format!("{}/articles/{}", self.site_url, article.slug)
+    }
+}
+
+pub fn render_rss(feed: &Feed) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push_str(r#"<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/"><channel>"#);
+    push_element(&mut xml, "title", feed.title);
+    push_element(&mut xml, "link", feed.site_url);
+    push_element(&mut xml, "description", feed.title);
+    if let Some(last_modified) = feed.last_modified {
+        push_element(
+            &mut xml,
+            "lastBuildDate",
+            &last_modified.and_utc().to_rfc2822(),
+        );
+    }
+
+    for article in feed.articles {
+        xml.push_str("<item>");
+        push_element(&mut xml, "title", &article.title);
+        push_element(&mut xml, "link", &feed.article_url(article));
+        _ = write!(
+            xml,
+            r#"<guid isPermaLink="false">urn:uuid:{}</guid>"#,
+            article.public_id
+        );
+        push_element(&mut xml, "description", &article.excerpt);
+        // `author` takes an email address, the username goes into Dublin Core instead.
+        push_element(&mut xml, "dc:creator", &article.author_username);
+        for tag in &article.tags {
+            push_element(&mut xml, "category", tag);
+        }
+        if let Some(publish_at) = article.publish_at {
+            push_element(
+                &mut xml,
+                "pubDate",
+                &publish_at.and_utc().to_rfc2822(),
+            );
+        }
+        xml.push_str("</item>");
+    }
+
+    xml.push_str("</channel></rss>");
+    xml
+}
+
+pub fn render_atom(feed: &Feed) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push_str(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#);
+    push_element(
+        &mut xml,
+        "id",
+        &format!("{}{}", feed.site_url, feed.path),
+    );
+    push_element(&mut xml, "title", feed.title);
+    // Atom requires the element, an empty feed was never updated.
+    let updated = feed
+        .last_modified
+        .unwrap_or(DateTime::UNIX_EPOCH.naive_utc());
+    push_element(&mut xml, "updated", &atom_date(updated));
+    _ = write!(
+        xml,
+        r#"<link href="{}"/>"#,
+        escape_xml(feed.site_url)
+    );
+
+    for article in feed.articles {
+        xml.push_str("<entry>");
+        _ = write!(xml, "<id>urn:uuid:{}</id>", article.public_id);
+        push_element(&mut xml, "title", &article.title);
+        _ = write!(
+            xml,
+            r#"<link href="{}"/>"#,
+            escape_xml(&feed.article_url(article))
+        );
+        let published = article.publish_at.unwrap_or(article.created_at);
+        push_element(&mut xml, "published", &atom_date(published));
+        push_element(
+            &mut xml,
+            "updated",
+            &atom_date(article.updated_at),
+        );
+        xml.push_str("<author>");
+        push_element(&mut xml, "name", &article.author_username);
+        xml.push_str("</author>");
+        for tag in &article.tags {
+            _ = write!(xml, r#"<category term="{}"/>"#, escape_xml(tag));
+        }
+        push_element(&mut xml, "summary", &article.excerpt);
+        xml.push_str("</entry>");
+    }
+
+    xml.push_str("</feed>");
+    xml
+}
+
+fn push_element(xml: &mut String, name: &str, text: &str) {
+    _ = write!(xml, "<{name}>{}</{name}>", escape_xml(text));
+}
+
+fn atom_date(timestamp: NaiveDateTime) -> String {
+    timestamp
+        .and_utc()
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Escapes markup and drops the characters XML 1.0 does not allow, the control characters but
+/// tab and line breaks and the `U+FFFE` and `U+FFFF` non-characters.
+fn escape_xml(text: &str) -> String {
+    text.chars()
+        .filter(|c| !is_forbidden_in_xml(*c))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn is_forbidden_in_xml(c: char) -> bool {
+    match c {
+        '\t' | '\n' | '\r' => false,
+        '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => true,
+        _ => false,
+    }
+}
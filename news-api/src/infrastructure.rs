@@ -1,113 +1,1091 @@
 use crate::app_state::DbPool;
+use crate::content_filter::{FilterRules, Submission, Verdict};
+use crate::content_render::render_content;
+use crate::errors::{
+    ArticleError, BookmarkError, ContributorError, ModerationError, SectionError, TagError,
+};
+use crate::languages::{search_config, search_configs};
+use crate::media_processing::extract_media_refs;
+use crate::pagination::{PageCursor, PageDirection, SortKey};
+use crate::slug::{is_slug_of, next_free_slug, slugify};
+use crate::tags::{escape_like, normalize_tags};
+use crate::view_recorder::ViewBucket;
 use anyhow::{anyhow, Result};
-use db_schema::models::{ArticleEntry, ArticleId, UserEntry, UserIdEntry};
+use db_schema::models::{
+    ArticleAccessEntry, ArticleBodyEntry, ArticleContentEntry, ArticleEntry, ArticleId,
+    ArticleOwnershipEntry, ArticlePageEntry, ArticlePublicationEntry, ArticleRevisionEntry,
+    ArticleSlugEntry, ArticleStatsBucketEntry, ArticleStatsTotalsEntry, ArticleSummaryEntry,
+    BookmarkEntry, BookmarkPageEntry, CommentKeyEntry, ContributorEntry,
+    ContributorInvitationEntry, CountEntry, LastModifiedEntry, MediaEntry, MediaId, MediaKeysEntry,
+    ReadingListEntry, ReadingListId, ReportEntry, ReportId, ReportTargetEntry,
+    ScheduledPublicationEntry, SectionEntry, SectionId, SectionKeyEntry, TagEntry, TagId,
+    UserEntry, UserIdEntry, UserRoleEntry,
+};
 use diesel::internal::derives::multiconnection::chrono::{NaiveDateTime, Utc};
-use diesel::sql_types::{Array, Int8, Integer, Text, Timestamp};
-use diesel::{sql_query, RunQueryDsl};
+use diesel::pg::Pg;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel::sql_types::{
+    Array, BigInt, Bool, Double, Int8, Integer, Nullable, Text, Timestamp, Uuid as SqlUuid,
+};
+use diesel::{sql_query, Connection, OptionalExtension, PgConnection, QueryResult, RunQueryDsl};
+use uuid::Uuid;
 
-pub fn create_article(
-    db_pool: &DbPool,
-    author_id: i32,
-    title: &str,
-    content: &str,
-    tag_names: Vec<String>,
-) -> Result<ArticleId> {
+/// Slugs tried before giving up on a slug taken concurrently over and over.
+const SLUG_ATTEMPTS: usize = 5;
+const SLUG_CONSTRAINTS: [&str; 2] = ["articles_slug_key", "article_slugs_pkey"];
+/// One live article per language of a translation group.
+const TRANSLATION_CONSTRAINTS: [&str; 1] = ["idx_articles_translation_group_id_language"];
+
+/// Fields of an article created by [`create_article`].
+pub struct NewArticle<'a> {
+    pub title: &'a str,
+    pub content: &'a str,
+    pub content_format: &'a str,
+    pub tags: Vec<String>,
+    pub status: &'a str,
+    pub publish_at: Option<NaiveDateTime>,
+    pub cover_media_id: Option<Uuid>,
+    /// The default section when `None`.
+    pub section_id: Option<Uuid>,
+    pub language: &'a str,
+    /// Article the new one translates, joining its translation group.
+    pub translation_of: Option<i64>,
+    /// Rules the article is checked against, held articles are hidden until a moderator approves
+    /// them.
+    pub content_filter: &'a FilterRules,
+}
+
+pub fn create_article(db_pool: &DbPool, author_id: i64, article: NewArticle) -> Result<ArticleId> {
     let conn = &mut db_pool.get_connection()?;
 
-    let article_id = sql_query(
+    conn.transaction(|conn| {
+        let rendered = render_content(article.content, article.content_format);
+        let cover_media_id = article
+            .cover_media_id
+            .map(|media_id| find_owned_media(conn, author_id, media_id))
+            .transpose()?;
+        let section_id = match article.section_id {
+            Some(section_id) => find_section(conn, section_id)?.id,
+            None => find_default_section(conn)?,
+        };
+        let translation_group_id = match article.translation_of {
+            Some(source_id) => {
+                let source = lock_article_access(conn, author_id, source_id, ArticleAccess::Editor)?;
+                ensure_translation_free(conn, source.translation_group_id, article.language, None)?;
+                source.translation_group_id
+            }
+            None => Uuid::now_v7(),
+        };
+        let held_by = filter_article(
+            conn,
+            article.content_filter,
+            author_id,
+            None,
+            Some(article.title),
+            Some(article.content),
+        )?;
+
+        let public_id = Uuid::now_v7();
+        let tags = normalize_tags(article.tags);
+        let media_ids = extract_media_refs(article.content);
+        let article_id = insert_with_free_slug(conn, &slugify(article.title), None, |conn, slug| {
+            sql_query(
+                r#"
+                WITH inserted_article AS (
+                    INSERT INTO articles (
+                        public_id, author_id, title, slug, content, content_format, content_html,
+                        excerpt, reading_time_minutes, status, publish_at, cover_media_id, section_id,
+                        language, search_config, translation_group_id
+                    )
+                        VALUES (
+                            $8, $1, $2, $7, $3, $9, $10, $11, $12, $5, $6, $13, $14, $15, $16::regconfig,
+                            $17
+                        )
+                        RETURNING id, public_id
+                ),
+                inserted_slug AS (
+                    INSERT INTO article_slugs (slug, article_id)
+                        SELECT $7, inserted_article.id
+                        FROM inserted_article
+                ),
+                inserted_tags AS (
+                     INSERT INTO tags (name)
+                         SELECT unnest($4::text[])
+                         ON CONFLICT (name) DO UPDATE
+                             SET name = EXCLUDED.name
+                         RETURNING id, name
+                 ),
+                 article_tag_associations AS (
+                     INSERT INTO articles_tags (article_id, tag_id)
+                         SELECT inserted_article.id, inserted_tags.id
+                         FROM inserted_article, inserted_tags
+                 ),
+                 inserted_revision AS (
+                     INSERT INTO article_revisions (
+                         article_id, revision, editor_id, title, content, content_format, tags,
+                         media_ids
+                     )
+                         SELECT inserted_article.id, 1, $1, $2, $3, $9, $4, $18
+                         FROM inserted_article
+                 ),
+                 inserted_contributor AS (
+                     INSERT INTO article_contributors (
+                         article_id, user_id, role, position, invited_by, accepted_at
+                     )
+                         SELECT inserted_article.id, $1, 'author', 1, $1, CURRENT_TIMESTAMP
+                         FROM inserted_article
+                 )
+                SELECT inserted_article.id, inserted_article.public_id
+                FROM inserted_article;
+            "#,
+            )
+            .bind::<BigInt, _>(author_id)
+            .bind::<Text, _>(article.title)
+            .bind::<Text, _>(article.content)
+            .bind::<Array<Text>, _>(&tags)
+            .bind::<Text, _>(article.status)
+            .bind::<Nullable<Timestamp>, _>(article.publish_at)
+            .bind::<Text, _>(slug)
+            .bind::<SqlUuid, _>(public_id)
+            .bind::<Text, _>(article.content_format)
+            .bind::<Text, _>(&rendered.html)
+            .bind::<Text, _>(&rendered.excerpt)
+            .bind::<Integer, _>(rendered.reading_time_minutes)
+            .bind::<Nullable<BigInt>, _>(cover_media_id)
+            .bind::<BigInt, _>(section_id)
+            .bind::<Text, _>(article.language)
+            .bind::<Text, _>(search_config(article.language))
+            .bind::<SqlUuid, _>(translation_group_id)
+            .bind::<Array<SqlUuid>, _>(&media_ids)
+            .get_result::<ArticleId>(conn)
+        })?;
+
+        replace_article_media(conn, article_id.id, media_ids)?;
+
+        if let Some(rule) = held_by {
+            hold_article(conn, article_id.id, &rule)?;
+        }
+
+        Ok(article_id)
+    })
+}
+
+/// Reserves a unique slug derived from `base`. Slugs already owned by `article_id` (current or
+/// former) can be reused, so renaming an article back does not grow a suffix.
+fn allocate_slug(conn: &mut PgConnection, base: &str, article_id: Option<i64>) -> Result<String> {
+    let taken = sql_query(
         r#"
-        WITH inserted_article AS (
-            INSERT INTO articles (author_id, title, content)
-                VALUES ($1, $2, $3)
-                RETURNING id
+        SELECT article_id, slug FROM article_slugs
+        WHERE (slug = $1 OR slug LIKE $1 || '-%') AND article_id IS DISTINCT FROM $2
+    "#,
+    )
+    .bind::<Text, _>(base)
+    .bind::<Nullable<BigInt>, _>(article_id)
+    .load::<ArticleSlugEntry>(conn)?
+    .into_iter()
+    .map(|entry| entry.slug)
+    .collect::<Vec<_>>();
+
+    Ok(next_free_slug(base, &taken))
+}
+
+/// Moves the article to a slug matching its new title, keeping the former one as a redirect.
+fn refresh_article_slug(conn: &mut PgConnection, article_id: i64, title: &str) -> Result<()> {
+    let base = slugify(title);
+
+    let current = sql_query(r#"SELECT id AS article_id, slug FROM articles WHERE id = $1"#)
+        .bind::<BigInt, _>(article_id)
+        .get_result::<ArticleSlugEntry>(conn)?;
+
+    if is_slug_of(&current.slug, &base) {
+        return Ok(());
+    }
+
+    insert_with_free_slug(conn, &base, Some(article_id), |conn, slug| {
+        // A former slug of the article is taken back as is, a slug of another one fails.
+        sql_query(
+            r#"
+            INSERT INTO article_slugs (slug, article_id)
+            SELECT $1, $2
+            WHERE NOT EXISTS (
+                SELECT 1 FROM article_slugs WHERE slug = $1 AND article_id = $2
+            )
+        "#,
+        )
+        .bind::<Text, _>(slug)
+        .bind::<BigInt, _>(article_id)
+        .execute(conn)?;
+
+        sql_query(r#"UPDATE articles SET slug = $1 WHERE id = $2"#)
+            .bind::<Text, _>(slug)
+            .bind::<BigInt, _>(article_id)
+            .execute(conn)
+    })?;
+
+    Ok(())
+}
+
+/// Runs `write` with the next free slug in a savepoint, over again with a fresh slug when a
+/// concurrent write took it first. A concurrent translation into the same language fails it.
+fn insert_with_free_slug<T>(
+    conn: &mut PgConnection,
+    base: &str,
+    article_id: Option<i64>,
+    mut write: impl FnMut(&mut PgConnection, &str) -> QueryResult<T>,
+) -> Result<T> {
+    for _ in 0..SLUG_ATTEMPTS {
+        let slug = allocate_slug(conn, base, article_id)?;
+
+        match conn.transaction(|conn| write(conn, &slug)) {
+            Err(err) if is_unique_violation(&err, &SLUG_CONSTRAINTS) => continue,
+            result => return result.map_err(translation_violation),
+        }
+    }
+
+    Err(anyhow!("[news-api] no free slug for {base}"))
+}
+
+/// Maps a write that lost the language of its translation group to a concurrent one to
+/// [`ArticleError::TranslationExists`].
+fn translation_violation(err: DieselError) -> anyhow::Error {
+    match is_unique_violation(&err, &TRANSLATION_CONSTRAINTS) {
+        true => ArticleError::TranslationExists.into(),
+        false => err.into(),
+    }
+}
+
+/// Whether the write failed on one of the unique constraints.
+fn is_unique_violation(err: &DieselError, constraints: &[&str]) -> bool {
+    match err {
+        DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => info
+            .constraint_name()
+            .is_some_and(|constraint| constraints.contains(&constraint)),
+        _ => false,
+    }
+}
+
+/// Columns of an article summary, selected from `articles` joined by [`ARTICLE_JOINS`]. Queries
+/// add `bookmarked_by_me` for their viewer. Tags and byline are collected per row rather than
+/// grouped, so a limit applies while walking the index of an order.
+const ARTICLE_COLUMNS: &str = r#"
+    articles.id,
+    articles.public_id,
+    articles.author_id,
+    articles.title,
+    articles.slug,
+    articles.content_format,
+    articles.excerpt,
+    articles.reading_time_minutes,
+    articles.status,
+    articles.publish_at,
+    articles.version,
+    articles.created_at,
+    articles.updated_at,
+    articles.deleted_at,
+    articles.view_count,
+    articles.language,
+    articles.translation_group_id,
+    articles.hidden_at IS NOT NULL AS hidden,
+    ARRAY(
+        SELECT tags.name
+        FROM articles_tags
+            JOIN tags ON tags.id = articles_tags.tag_id
+        WHERE articles_tags.article_id = articles.id
+        ORDER BY tags.name
+    ) AS tags,
+    cover_media.public_id AS cover_media_id,
+    COALESCE(
+        (
+            SELECT jsonb_agg(
+                jsonb_build_object(
+                    'user_id', contributors.public_id,
+                    'role', article_contributors.role,
+                    'username', contributors.username
+                )
+                ORDER BY article_contributors.position
+            )
+            FROM article_contributors
+                JOIN users AS contributors ON contributors.id = article_contributors.user_id
+            WHERE article_contributors.article_id = articles.id
+                AND article_contributors.accepted_at IS NOT NULL
         ),
-        inserted_tags AS (
-             INSERT INTO tags (name)
-                 SELECT unnest($4::text[])
-                 ON CONFLICT (name) DO UPDATE
-                     SET name = EXCLUDED.name
-                 RETURNING id, name
-         ),
-         article_tag_associations AS (
-             INSERT INTO articles_tags (article_id, tag_id)
-                 SELECT inserted_article.id, inserted_tags.id
-                 FROM inserted_article, inserted_tags
-         )
-        SELECT inserted_article.id
-        FROM inserted_article;
-    "#,
-    )
-    .bind::<Integer, _>(author_id)
-    .bind::<Text, _>(title)
-    .bind::<Text, _>(content)
-    .bind::<Array<Text>, _>(tag_names)
-    .get_result::<ArticleId>(conn)?;
+        '[]'
+    ) AS byline,
+    users.public_id AS author_public_id,
+    users.username AS author_username,
+    sections.public_id AS section_public_id,
+    sections.path AS section_path
+"#;
+
+/// Columns a full article has on top of [`ARTICLE_COLUMNS`].
+const ARTICLE_CONTENT_COLUMNS: &str = r#"
+    articles.content,
+    articles.content_html,
+    ARRAY(
+        SELECT media.public_id
+        FROM articles_media
+            JOIN media ON media.id = articles_media.media_id
+        WHERE articles_media.article_id = articles.id
+        ORDER BY media.id
+    ) AS media_ids
+"#;
+
+/// Joins of `articles` the columns of [`ARTICLE_COLUMNS`] come from.
+const ARTICLE_JOINS: &str = r#"
+    LEFT JOIN users ON users.id = articles.author_id
+    LEFT JOIN media AS cover_media ON cover_media.id = articles.cover_media_id
+    JOIN sections ON sections.id = articles.section_id
+"#;
+
+/// Condition that the user bound at `user` authored `articles` or is an accepted contributor.
+fn contributed_by(user: &str) -> String {
+    format!(
+        r#"(
+            articles.author_id = {user}
+            OR EXISTS (
+                SELECT 1 FROM article_contributors
+                WHERE article_contributors.article_id = articles.id
+                    AND article_contributors.user_id = {user}
+                    AND article_contributors.accepted_at IS NOT NULL
+            )
+        )"#
+    )
+}
+
+/// Condition that `articles` is not hidden by moderators, or shown anyway to the viewer bound at
+/// `viewer` as its author or contributor, or to everyone when `include_hidden` holds.
+fn shown_to(viewer: &str, include_hidden: &str) -> String {
+    format!(
+        "(articles.hidden_at IS NULL OR {include_hidden} OR {})",
+        contributed_by(viewer)
+    )
+}
+
+/// Condition that the viewer bound at `viewer` may read `articles`, published ones and their own.
+fn visible_to(viewer: &str) -> String {
+    format!(
+        "(articles.status = 'published' OR {})",
+        contributed_by(viewer)
+    )
+}
+
+/// Optional filters of [`get_articles_page`], empty fields don't restrict the page.
+#[derive(Default, Debug)]
+pub struct ArticleFilter {
+    pub tags: Vec<String>,
+    pub match_all_tags: bool,
+    pub author_id: Option<Uuid>,
+    pub author_username: Option<String>,
+    /// The section and its subsections, by public id or by path.
+    pub section_id: Option<Uuid>,
+    pub section_path: Option<String>,
+    pub created_after: Option<NaiveDateTime>,
+    pub created_before: Option<NaiveDateTime>,
+    pub language: Option<String>,
+    pub translation_group_id: Option<Uuid>,
+    /// Full-text search query in web search syntax.
+    pub query: Option<String>,
+    /// Also articles hidden by moderators, for moderators.
+    pub include_hidden: bool,
+}
+
+/// Order of [`get_articles_page`], scores come from `article_scores` kept by the score refresher.
+/// Pages by score are keyed by the score an article had when the page was loaded, one fetched
+/// after a refresh may skip or repeat the articles whose score changed in between.
+#[derive(Clone, Copy)]
+pub enum ArticleOrder {
+    /// By publication time.
+    Newest,
+    /// By likes, of articles published after the given time when set.
+    Top {
+        published_after: Option<NaiveDateTime>,
+    },
+    /// By the time-decayed score of likes and comments.
+    Trending,
+}
+
+impl ArticleOrder {
+    fn sort_expression(&self) -> &'static str {
+        match self {
+            ArticleOrder::Newest => "articles.publish_at",
+            ArticleOrder::Top { .. } => "article_scores.like_count",
+            ArticleOrder::Trending => "article_scores.trending_score",
+        }
+    }
 
-    Ok(article_id)
+    /// Tie-breaker of the sort expression, taken from the same table so one index serves both.
+    fn id_expression(&self) -> &'static str {
+        match self {
+            ArticleOrder::Newest => "articles.id",
+            ArticleOrder::Top { .. } | ArticleOrder::Trending => "article_scores.article_id",
+        }
+    }
+
+    /// Sorting by score walks the score indexes, so only scored articles are listed, the ones
+    /// published since the last refresh join in with the next one.
+    fn scores_join(&self) -> &'static str {
+        match self {
+            ArticleOrder::Newest => "LEFT JOIN",
+            ArticleOrder::Top { .. } | ArticleOrder::Trending => "JOIN",
+        }
+    }
 }
 
+/// Loads published articles in the given order, keyed by `(sort key, id)` so that articles
+/// sharing a sort key are neither skipped nor repeated across pages.
 pub fn get_articles_page(
     db_pool: &DbPool,
-    last_timestamp: Option<NaiveDateTime>,
-    page_size: i64,
-) -> Result<Vec<ArticleEntry>> {
+    order: ArticleOrder,
+    cursor: Option<&PageCursor<(SortKey, i64)>>,
+    limit: i64,
+    filter: ArticleFilter,
+    viewer_id: Option<i64>,
+) -> Result<Vec<ArticlePageEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+    let sort_expression = order.sort_expression();
+    let id_expression = order.id_expression();
+    let scores_join = order.scores_join();
+
+    let tags = normalize_tags(filter.tags);
+
+    // Only the requested conditions end up in the query, so the planner can pick the tag and
+    // author indexes instead of planning around `$n IS NULL OR ...` branches.
+    let mut conditions = String::new();
+    let mut placeholder = 2;
+    let mut next_placeholder = || {
+        placeholder += 1;
+        placeholder
+    };
+
+    let direction = match cursor.map(|cursor| cursor.direction) {
+        None => "DESC",
+        Some(PageDirection::Next) => {
+            conditions.push_str(&format!(
+                "\n            AND ({sort_expression}, {id_expression}) < (${}, ${})",
+                next_placeholder(),
+                next_placeholder()
+            ));
+            "DESC"
+        }
+        Some(PageDirection::Prev) => {
+            conditions.push_str(&format!(
+                "\n            AND ({sort_expression}, {id_expression}) > (${}, ${})",
+                next_placeholder(),
+                next_placeholder()
+            ));
+            "ASC"
+        }
+    };
+
+    if let ArticleOrder::Top {
+        published_after: Some(_),
+    } = order
+    {
+        conditions.push_str(&format!(
+            "\n            AND articles.publish_at > ${}",
+            next_placeholder()
+        ));
+    }
+    if !tags.is_empty() {
+        let tags_placeholder = next_placeholder();
+        let having = match filter.match_all_tags {
+            true => format!("HAVING COUNT(*) = ${}", next_placeholder()),
+            false => String::new(),
+        };
+        conditions.push_str(&format!(
+            r#"
+            AND articles.id IN (
+                SELECT articles_tags.article_id
+                FROM tags
+                    JOIN articles_tags ON articles_tags.tag_id = tags.id
+                WHERE tags.name = ANY(${tags_placeholder})
+                GROUP BY articles_tags.article_id
+                {having}
+            )"#
+        ));
+    }
+    if filter.author_id.is_some() {
+        conditions.push_str(&format!(
+            "\n            AND articles.author_id = (SELECT id FROM users WHERE public_id = ${})",
+            next_placeholder()
+        ));
+    }
+    if filter.author_username.is_some() {
+        conditions.push_str(&format!(
+            "\n            AND articles.author_id = (SELECT id FROM users WHERE username = ${})",
+            next_placeholder()
+        ));
+    }
+    if filter.section_id.is_some() {
+        conditions.push_str(&format!(
+            "\n            AND articles.section_id IN ({SECTION_TREE_IDS} WHERE sections.public_id = ${})",
+            next_placeholder()
+        ));
+    }
+    if filter.section_path.is_some() {
+        conditions.push_str(&format!(
+            "\n            AND articles.section_id IN ({SECTION_TREE_IDS} WHERE sections.path = ${})",
+            next_placeholder()
+        ));
+    }
+    if filter.created_after.is_some() {
+        conditions.push_str(&format!(
+            "\n            AND articles.created_at > ${}",
+            next_placeholder()
+        ));
+    }
+    if filter.created_before.is_some() {
+        conditions.push_str(&format!(
+            "\n            AND articles.created_at < ${}",
+            next_placeholder()
+        ));
+    }
+    if !filter.include_hidden {
+        conditions.push_str(&format!(
+            "\n            AND {}",
+            shown_to("$2", "FALSE")
+        ));
+    }
+    if filter.language.is_some() {
+        conditions.push_str(&format!(
+            "\n            AND articles.language = ${}",
+            next_placeholder()
+        ));
+    }
+    if filter.translation_group_id.is_some() {
+        conditions.push_str(&format!(
+            "\n            AND articles.translation_group_id = ${}",
+            next_placeholder()
+        ));
+    }
+    if filter.query.is_some() {
+        // The query is parsed once per configuration so the search vector index applies, without
+        // a language it is matched against the articles of each configuration in turn.
+        let matches = match filter.language {
+            Some(_) => {
+                let (config, search_query) = (next_placeholder(), next_placeholder());
+                format!(
+                    "articles.search_vector @@ websearch_to_tsquery(${config}::regconfig, ${search_query})"
+                )
+            }
+            None => {
+                let search_query = next_placeholder();
+                search_configs()
+                    .map(|config| {
+                        format!(
+                            "(articles.search_config = '{config}'::regconfig \
+                                AND articles.search_vector @@ websearch_to_tsquery('{config}', ${search_query}))"
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n                OR ")
+            }
+        };
+        conditions.push_str(&format!("\n            AND ({matches})"));
+    }
+
+    let mut query = sql_query(format!(
+        r#"
+        SELECT
+            {ARTICLE_COLUMNS},
+            EXISTS (
+                SELECT 1 FROM bookmarks
+                WHERE bookmarks.article_id = articles.id AND bookmarks.user_id = $2
+            ) AS bookmarked_by_me,
+            COALESCE(article_scores.like_count, 0) AS like_count,
+            COALESCE(article_scores.trending_score, 0) AS trending_score
+        FROM articles
+            {ARTICLE_JOINS}
+            {scores_join} article_scores ON article_scores.article_id = articles.id
+        WHERE articles.status = 'published'
+            AND articles.deleted_at IS NULL{conditions}
+        ORDER BY {sort_expression} {direction}, {id_expression} {direction}
+        LIMIT $1
+    "#
+    ))
+    .into_boxed::<Pg>()
+    .bind::<Int8, _>(limit)
+    .bind::<Nullable<BigInt>, _>(viewer_id);
+
+    if let Some(cursor) = cursor {
+        let (sort_key, id) = cursor.position;
+        query = match sort_key {
+            SortKey::Timestamp(timestamp) => query.bind::<Timestamp, _>(timestamp),
+            SortKey::Count(count) => query.bind::<BigInt, _>(count),
+            SortKey::Score(score) => query.bind::<Double, _>(score),
+        }
+        .bind::<BigInt, _>(id);
+    }
+    if let ArticleOrder::Top {
+        published_after: Some(published_after),
+    } = order
+    {
+        query = query.bind::<Timestamp, _>(published_after);
+    }
+
+    if !tags.is_empty() {
+        let tags_count = tags.len() as i64;
+        query = query.bind::<Array<Text>, _>(tags);
+        if filter.match_all_tags {
+            query = query.bind::<BigInt, _>(tags_count);
+        }
+    }
+    if let Some(author_id) = filter.author_id {
+        query = query.bind::<SqlUuid, _>(author_id);
+    }
+    if let Some(author_username) = filter.author_username {
+        query = query.bind::<Text, _>(author_username);
+    }
+    if let Some(section_id) = filter.section_id {
+        query = query.bind::<SqlUuid, _>(section_id);
+    }
+    if let Some(section_path) = filter.section_path {
+        query = query.bind::<Text, _>(section_path);
+    }
+    if let Some(created_after) = filter.created_after {
+        query = query.bind::<Timestamp, _>(created_after);
+    }
+    if let Some(created_before) = filter.created_before {
+        query = query.bind::<Timestamp, _>(created_before);
+    }
+    if let Some(language) = &filter.language {
+        query = query.bind::<Text, _>(language.clone());
+    }
+    if let Some(translation_group_id) = filter.translation_group_id {
+        query = query.bind::<SqlUuid, _>(translation_group_id);
+    }
+    if let Some(search_query) = filter.query {
+        if let Some(language) = &filter.language {
+            query = query.bind::<Text, _>(search_config(language));
+        }
+        query = query.bind::<Text, _>(search_query);
+    }
+
+    let articles = query.load::<ArticlePageEntry>(conn)?;
+
+    Ok(articles)
+}
+
+/// Time of the last change to the articles of a feed, optionally those with the tag or by the
+/// author. Unpublished, trashed and hidden articles count too, as dropping out of the feed is a
+/// change of it.
+pub fn get_feed_last_modified(
+    db_pool: &DbPool,
+    tag: Option<&str>,
+    author_username: Option<&str>,
+) -> Result<Option<NaiveDateTime>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let mut conditions = String::new();
+    let mut placeholder = 0;
+    let mut next_placeholder = || {
+        placeholder += 1;
+        placeholder
+    };
+
+    if tag.is_some() {
+        conditions.push_str(&format!(
+            r#"
+            AND articles.id IN (
+                SELECT articles_tags.article_id
+                FROM tags
+                    JOIN articles_tags ON articles_tags.tag_id = tags.id
+                WHERE tags.name = ${}
+            )"#,
+            next_placeholder()
+        ));
+    }
+    if author_username.is_some() {
+        conditions.push_str(&format!(
+            "\n            AND articles.author_id = (SELECT id FROM users WHERE username = ${})",
+            next_placeholder()
+        ));
+    }
+
+    let mut query = sql_query(format!(
+        r#"
+        SELECT MAX(articles.updated_at) AS last_modified
+        FROM articles
+        WHERE TRUE{conditions}
+    "#
+    ))
+    .into_boxed::<Pg>();
+
+    if let Some(tag) = tag {
+        query = query.bind::<Text, _>(tag.to_string());
+    }
+    if let Some(author_username) = author_username {
+        query = query.bind::<Text, _>(author_username.to_string());
+    }
+
+    let last_modified = query.get_result::<LastModifiedEntry>(conn)?;
+
+    Ok(last_modified.last_modified)
+}
+
+/// Recomputes the like and comment counts of published articles and their trending score, which
+/// decays with the hours since publication as
+/// `(likes + 2 * comments + views / 10) / (hours + 2) ^ 1.5`.
+pub fn refresh_article_scores(db_pool: &DbPool, now: NaiveDateTime) -> Result<usize> {
     let conn = &mut db_pool.get_connection()?;
 
-    let timestamp = last_timestamp.unwrap_or_else(|| Utc::now().naive_utc());
-    let articles = sql_query(
+    let refreshed = sql_query(
         r#"
+        INSERT INTO article_scores (article_id, like_count, comment_count, trending_score, updated_at)
         SELECT
             articles.id,
-            articles.author_id,
-            articles.title,
-            articles.content,
-            articles.created_at,
-            array_agg(tags.name) AS tags,
-            users.username AS author_username
+            COALESCE(likes.count, 0),
+            COALESCE(comments.count, 0),
+            (COALESCE(likes.count, 0) + 2 * COALESCE(comments.count, 0) + articles.view_count / 10.0)
+                / power(GREATEST(EXTRACT(EPOCH FROM $1 - articles.publish_at) / 3600, 0) + 2, 1.5),
+            $1
         FROM articles
-            LEFT JOIN articles_tags ON articles.id = articles_tags.article_id
-            LEFT JOIN tags ON tags.id = articles_tags.tag_id
-            LEFT JOIN users ON users.id = articles.author_id
-        WHERE articles.created_at < $1
-        GROUP BY articles.id, articles.created_at, users.username
-        ORDER BY articles.created_at DESC
-        LIMIT $2
+            LEFT JOIN (
+                SELECT article_id, COUNT(*) AS count FROM likes GROUP BY article_id
+            ) AS likes ON likes.article_id = articles.id
+            LEFT JOIN (
+                SELECT article_id, COUNT(*) AS count FROM comments
+                WHERE hidden_at IS NULL AND deleted_at IS NULL
+                GROUP BY article_id
+            ) AS comments ON comments.article_id = articles.id
+        WHERE articles.status = 'published'
+            AND articles.deleted_at IS NULL
+        ON CONFLICT (article_id) DO UPDATE SET
+            like_count = EXCLUDED.like_count,
+            comment_count = EXCLUDED.comment_count,
+            trending_score = EXCLUDED.trending_score,
+            updated_at = EXCLUDED.updated_at
     "#,
     )
-    .bind::<Timestamp, _>(timestamp)
-    .bind::<Int8, _>(page_size)
-    .load::<ArticleEntry>(conn)?;
+    .bind::<Timestamp, _>(now)
+    .execute(conn)?;
 
-    Ok(articles)
+    Ok(refreshed)
 }
 
-pub fn update_article(
+/// Adds flushed view buckets to the hourly view counts and the article totals. Views of articles
+/// purged in the meantime are dropped.
+pub fn record_article_views(db_pool: &DbPool, buckets: &[ViewBucket]) -> Result<()> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let article_ids = buckets
+        .iter()
+        .map(|bucket| bucket.article_id)
+        .collect::<Vec<_>>();
+    let bucket_starts = buckets
+        .iter()
+        .map(|bucket| bucket.bucket_start)
+        .collect::<Vec<_>>();
+    let view_counts = buckets
+        .iter()
+        .map(|bucket| bucket.view_count)
+        .collect::<Vec<_>>();
+
+    conn.transaction(|conn| {
+        sql_query(
+            r#"
+            INSERT INTO article_view_buckets (article_id, bucket_start, view_count)
+            SELECT views.article_id, views.bucket_start, views.view_count
+            FROM unnest($1, $2, $3) AS views(article_id, bucket_start, view_count)
+                JOIN articles ON articles.id = views.article_id
+            ON CONFLICT (article_id, bucket_start) DO UPDATE
+                SET view_count = article_view_buckets.view_count + EXCLUDED.view_count
+            "#,
+        )
+        .bind::<Array<BigInt>, _>(&article_ids)
+        .bind::<Array<Timestamp>, _>(&bucket_starts)
+        .bind::<Array<BigInt>, _>(&view_counts)
+        .execute(conn)?;
+
+        sql_query(
+            r#"
+            UPDATE articles
+            SET view_count = articles.view_count + views.view_count
+            FROM (
+                SELECT article_id, SUM(view_count)::BIGINT AS view_count
+                FROM unnest($1, $2) AS views(article_id, view_count)
+                GROUP BY article_id
+            ) AS views
+            WHERE articles.id = views.article_id
+            "#,
+        )
+        .bind::<Array<BigInt>, _>(&article_ids)
+        .bind::<Array<BigInt>, _>(&view_counts)
+        .execute(conn)?;
+
+        Ok(())
+    })
+}
+
+/// Loads the all-time totals of the author's article and its views, likes and comments per
+/// `hour` or `day` bucket between the given times.
+pub fn get_article_stats(
     db_pool: &DbPool,
-    author_id: i32,
-    article_id: i32,
-    title: &str,
-    content: &str,
-    tag_names: Vec<String>,
-) -> Result<()> {
+    author_id: i64,
+    article_id: i64,
+    interval: &str,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+) -> Result<(
+    ArticleStatsTotalsEntry,
+    Vec<ArticleStatsBucketEntry>,
+)> {
     let conn = &mut db_pool.get_connection()?;
 
-    sql_query(
+    let article = sql_query(
+        r#"
+        SELECT author_id, version FROM articles
+        WHERE id = $1 AND deleted_at IS NULL
+    "#,
+    )
+    .bind::<BigInt, _>(article_id)
+    .get_result::<ArticleOwnershipEntry>(conn)
+    .optional()?
+    .ok_or(ArticleError::NotFound)?;
+
+    if article.author_id != author_id {
+        return Err(ArticleError::PermissionDenied.into());
+    }
+
+    let totals = sql_query(
+        r#"
+        SELECT
+            articles.view_count,
+            (SELECT COUNT(*) FROM likes WHERE likes.article_id = articles.id) AS like_count,
+            (
+                SELECT COUNT(*) FROM comments
+                WHERE comments.article_id = articles.id
+                    AND comments.hidden_at IS NULL
+                    AND comments.deleted_at IS NULL
+            ) AS comment_count
+        FROM articles
+        WHERE articles.id = $1
+    "#,
+    )
+    .bind::<BigInt, _>(article_id)
+    .get_result::<ArticleStatsTotalsEntry>(conn)?;
+
+    let buckets = sql_query(
         r#"
-        WITH updated_article AS (
+        SELECT
+            buckets.bucket_start,
+            (
+                SELECT COALESCE(SUM(article_view_buckets.view_count), 0)::BIGINT
+                FROM article_view_buckets
+                WHERE article_view_buckets.article_id = $1
+                    AND article_view_buckets.bucket_start >= buckets.bucket_start
+                    AND article_view_buckets.bucket_start < buckets.bucket_start + ('1 ' || $2)::INTERVAL
+            ) AS view_count,
+            (
+                SELECT COUNT(*)
+                FROM likes
+                WHERE likes.article_id = $1
+                    AND likes.created_at >= buckets.bucket_start
+                    AND likes.created_at < buckets.bucket_start + ('1 ' || $2)::INTERVAL
+            ) AS like_count,
+            (
+                SELECT COUNT(*)
+                FROM comments
+                WHERE comments.article_id = $1
+                    AND comments.created_at >= buckets.bucket_start
+                    AND comments.created_at < buckets.bucket_start + ('1 ' || $2)::INTERVAL
+                    AND comments.hidden_at IS NULL
+                    AND comments.deleted_at IS NULL
+            ) AS comment_count
+        FROM generate_series(date_trunc($2, $3), $4, ('1 ' || $2)::INTERVAL) AS buckets(bucket_start)
+        ORDER BY buckets.bucket_start
+    "#,
+    )
+    .bind::<BigInt, _>(article_id)
+    .bind::<Text, _>(interval)
+    .bind::<Timestamp, _>(start)
+    .bind::<Timestamp, _>(end)
+    .load::<ArticleStatsBucketEntry>(conn)?;
+
+    Ok((totals, buckets))
+}
+
+/// Fields to change in [`update_article`], `None` leaves the stored value untouched.
+pub struct ArticleChanges {
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub content_format: Option<String>,
+    pub tags: Option<Vec<String>>,
+    /// `Some(None)` removes the cover.
+    pub cover_media_id: Option<Option<Uuid>>,
+    pub section_id: Option<Uuid>,
+    pub language: Option<String>,
+}
+
+/// Changes an article of the author or of an article the user co-authors or edits, revisions
+/// record the user as their editor, along with whether its tags changed. `held_by` names the
+/// content filter rule holding the changed article for review.
+pub fn update_article(
+    db_pool: &DbPool,
+    editor_id: i64,
+    article_id: i64,
+    expected_version: Option<i32>,
+    changes: ArticleChanges,
+    content_filter: &FilterRules,
+) -> Result<(ArticleEntry, bool)> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let current = lock_article_for_write(
+            conn,
+            editor_id,
+            article_id,
+            expected_version,
+            ArticleAccess::Editor,
+        )?;
+        let held_by = filter_article(
+            conn,
+            content_filter,
+            current.author_id,
+            Some(article_id),
+            changes.title.as_deref(),
+            changes.content.as_deref(),
+        )?;
+
+        if let Some(title) = &changes.title {
+            refresh_article_slug(conn, article_id, title)?;
+        }
+
+        let content_changed = changes.content.is_some() || changes.content_format.is_some();
+        let cover_media_id = changes
+            .cover_media_id
+            .map(|cover| {
+                cover
+                    .map(|media_id| find_contributor_media(conn, article_id, media_id))
+                    .transpose()
+            })
+            .transpose()?;
+        let section_id = changes
+            .section_id
+            .map(|section_id| find_section(conn, section_id).map(|section| section.id))
+            .transpose()?;
+        if let Some(language) = &changes.language {
+            ensure_translation_free(
+                conn,
+                current.translation_group_id,
+                language,
+                Some(article_id),
+            )?;
+        }
+
+        let updated = sql_query(
+            r#"
             UPDATE articles
-            SET title = $1, content = $2
-            WHERE id = $4 AND author_id = $3
-            RETURNING id
-        ),
-        existing_tags AS (
+            SET
+                title = COALESCE($1, title),
+                content = COALESCE($2, content),
+                content_format = COALESCE($4, content_format),
+                cover_media_id = CASE WHEN $5 THEN $6 ELSE cover_media_id END,
+                section_id = COALESCE($7, section_id),
+                language = COALESCE($8, language),
+                search_config = COALESCE($9::regconfig, search_config),
+                version = version + 1
+            WHERE id = $3
+            RETURNING content, content_format
+            "#,
+        )
+        .bind::<Nullable<Text>, _>(changes.title)
+        .bind::<Nullable<Text>, _>(changes.content)
+        .bind::<BigInt, _>(article_id)
+        .bind::<Nullable<Text>, _>(changes.content_format)
+        .bind::<Bool, _>(cover_media_id.is_some())
+        .bind::<Nullable<BigInt>, _>(cover_media_id.flatten())
+        .bind::<Nullable<BigInt>, _>(section_id)
+        .bind::<Nullable<Text>, _>(changes.language.as_deref())
+        .bind::<Nullable<Text>, _>(changes.language.as_deref().map(search_config))
+        .get_result::<ArticleContentEntry>(conn)
+        .map_err(translation_violation)?;
+
+        if content_changed {
+            let rendered = render_content(&updated.content, &updated.content_format);
+
+            sql_query(
+                r#"
+                UPDATE articles
+                SET content_html = $2, excerpt = $3, reading_time_minutes = $4
+                WHERE id = $1
+                "#,
+            )
+            .bind::<BigInt, _>(article_id)
+            .bind::<Text, _>(rendered.html)
+            .bind::<Text, _>(rendered.excerpt)
+            .bind::<Integer, _>(rendered.reading_time_minutes)
+            .execute(conn)?;
+
+            replace_article_media(
+                conn,
+                article_id,
+                extract_media_refs(&updated.content),
+            )?;
+        }
+
+        let tags_changed = match changes.tags {
+            Some(tag_names) => replace_article_tags(conn, article_id, tag_names)?,
+            None => false,
+        };
+
+        // Revisions snapshot the resulting article and are numbered by its version.
+        sql_query(
+            r#"
+            INSERT INTO article_revisions (
+                article_id, revision, editor_id, title, content, content_format, tags, media_ids
+            )
+            SELECT
+                articles.id,
+                articles.version,
+                $2,
+                articles.title,
+                articles.content,
+                articles.content_format,
+                ARRAY(
+                    SELECT tags.name
+                    FROM articles_tags
+                        JOIN tags ON tags.id = articles_tags.tag_id
+                    WHERE articles_tags.article_id = articles.id
+                    ORDER BY tags.name
+                ),
+                $3
+            FROM articles
+            WHERE articles.id = $1
+            "#,
+        )
+        .bind::<BigInt, _>(article_id)
+        .bind::<BigInt, _>(editor_id)
+        .bind::<Array<SqlUuid>, _>(extract_media_refs(&updated.content))
+        .execute(conn)?;
+
+        if let Some(rule) = held_by {
+            hold_article(conn, article_id, &rule)?;
+        }
+
+        let article = find_article(conn, article_id, Some(editor_id), false)?;
+
+        Ok((article, tags_changed))
+    })
+}
+
+/// Replaces the tags of an article, telling whether any was added or removed.
+fn replace_article_tags(
+    conn: &mut PgConnection,
+    article_id: i64,
+    tag_names: Vec<String>,
+) -> Result<bool> {
+    let changed = sql_query(
+        r#"
+        WITH existing_tags AS (
             SELECT t.id, t.name
             FROM tags t
             JOIN articles_tags at ON t.id = at.tag_id
-            WHERE at.article_id = $4
+            WHERE at.article_id = $1
         ),
         new_tags AS (
             INSERT INTO tags (name)
-            SELECT unnest($5::text[])
+            SELECT unnest($2::text[])
             ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
             RETURNING id, name
         ),
@@ -125,93 +1103,2431 @@ pub fn update_article(
         ),
         deleted_article_tags AS (
             DELETE FROM articles_tags
-            WHERE article_id = $4 AND tag_id IN (SELECT id FROM tags_to_remove)
+            WHERE article_id = $1 AND tag_id IN (SELECT id FROM tags_to_remove)
         ),
         inserted_article_tags AS (
             INSERT INTO articles_tags (article_id, tag_id)
-            SELECT $4, id FROM tags_to_add
+            SELECT $1, id FROM tags_to_add
             ON CONFLICT DO NOTHING
         ),
         deleted_orphaned_tags AS (
             DELETE FROM tags
             WHERE id IN (SELECT id FROM tags_to_remove)
               AND NOT EXISTS (
-                  SELECT 1 FROM articles_tags WHERE tag_id = tags.id AND article_id != $4
+                  SELECT 1 FROM articles_tags WHERE tag_id = tags.id AND article_id != $1
               )
         )
-        SELECT 1;
+        SELECT (SELECT COUNT(*) FROM tags_to_add) + (SELECT COUNT(*) FROM tags_to_remove) AS count;
         "#,
     )
-    .bind::<Text, _>(title)
-    .bind::<Text, _>(content)
-    .bind::<Integer, _>(author_id)
-    .bind::<Integer, _>(article_id)
-    .bind::<Array<Text>, _>(tag_names)
-    .execute(conn)?;
+    .bind::<BigInt, _>(article_id)
+    .bind::<Array<Text>, _>(normalize_tags(tag_names))
+    .get_result::<CountEntry>(conn)?;
 
-    Ok(())
+    Ok(changed.count > 0)
+}
+
+pub fn delete_article(
+    db_pool: &DbPool,
+    author_id: i64,
+    article_id: i64,
+    expected_version: Option<i32>,
+) -> Result<ArticleEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        lock_article_for_write(
+            conn,
+            author_id,
+            article_id,
+            expected_version,
+            ArticleAccess::Author,
+        )?;
+
+        let mut article = find_article(conn, article_id, Some(author_id), false)?;
+        let deleted_at = Utc::now().naive_utc();
+
+        sql_query(
+            r#"
+            UPDATE articles
+            SET deleted_at = $3, deleted_by = $2
+            WHERE id = $1
+            "#,
+        )
+        .bind::<BigInt, _>(article_id)
+        .bind::<BigInt, _>(author_id)
+        .bind::<Timestamp, _>(deleted_at)
+        .execute(conn)?;
+
+        article.deleted_at = Some(deleted_at);
+
+        Ok(article)
+    })
+}
+
+pub fn restore_article(db_pool: &DbPool, author_id: i64, article_id: i64) -> Result<ArticleEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let trashed = sql_query(
+            r#"
+            SELECT author_id, version FROM articles
+            WHERE id = $1 AND deleted_at IS NOT NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind::<BigInt, _>(article_id)
+        .get_result::<ArticleOwnershipEntry>(conn)
+        .optional()?
+        .ok_or(ArticleError::NotFound)?;
+
+        if trashed.author_id != author_id {
+            return Err(ArticleError::PermissionDenied.into());
+        }
+
+        let moderated = sql_query(
+            r#"SELECT 1 FROM articles WHERE id = $1 AND deleted_by IS DISTINCT FROM author_id"#,
+        )
+        .bind::<BigInt, _>(article_id)
+        .execute(conn)?;
+
+        if moderated > 0 {
+            return Err(ArticleError::Moderated.into());
+        }
+
+        // Another translation may have taken over the language in the meantime.
+        let taken = sql_query(
+            r#"
+            SELECT 1 FROM articles AS trashed
+                JOIN articles ON articles.translation_group_id = trashed.translation_group_id
+                    AND articles.language = trashed.language
+            WHERE trashed.id = $1 AND articles.deleted_at IS NULL
+        "#,
+        )
+        .bind::<BigInt, _>(article_id)
+        .execute(conn)?;
+
+        if taken > 0 {
+            return Err(ArticleError::TranslationExists.into());
+        }
+
+        sql_query(
+            r#"
+            UPDATE articles
+            SET deleted_at = NULL, deleted_by = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind::<BigInt, _>(article_id)
+        .execute(conn)
+        .map_err(translation_violation)?;
+
+        let article = find_article(conn, article_id, Some(author_id), false)?;
+
+        Ok(article)
+    })
+}
+
+pub fn get_deleted_articles(db_pool: &DbPool, author_id: i64) -> Result<Vec<ArticleSummaryEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let articles = sql_query(format!(
+        r#"
+        SELECT
+            {ARTICLE_COLUMNS},
+            EXISTS (
+                SELECT 1 FROM bookmarks
+                WHERE bookmarks.article_id = articles.id AND bookmarks.user_id = $1
+            ) AS bookmarked_by_me
+        FROM articles
+            {ARTICLE_JOINS}
+        WHERE articles.author_id = $1 AND articles.deleted_at IS NOT NULL
+        ORDER BY articles.deleted_at DESC
+    "#
+    ))
+    .bind::<BigInt, _>(author_id)
+    .load::<ArticleSummaryEntry>(conn)?;
+
+    Ok(articles)
 }
 
-pub fn delete_article(db_pool: &DbPool, author_id: i32, article_id: i32) -> Result<()> {
+/// Loads the content of the given articles for list views that ask for it.
+pub fn get_article_bodies(db_pool: &DbPool, article_ids: &[i64]) -> Result<Vec<ArticleBodyEntry>> {
     let conn = &mut db_pool.get_connection()?;
 
+    let bodies = sql_query(
+        r#"
+        SELECT
+            articles.id,
+            articles.content,
+            articles.content_html,
+            ARRAY(
+                SELECT media.public_id
+                FROM articles_media
+                    JOIN media ON media.id = articles_media.media_id
+                WHERE articles_media.article_id = articles.id
+                ORDER BY media.id
+            ) AS media_ids
+        FROM articles
+        WHERE articles.id = ANY($1)
+    "#,
+    )
+    .bind::<Array<BigInt>, _>(article_ids)
+    .load::<ArticleBodyEntry>(conn)?;
+
+    Ok(bodies)
+}
+
+/// Links the article to the media referenced inline in its content, only uploads of its accepted
+/// contributors are linked.
+fn replace_article_media(
+    conn: &mut PgConnection,
+    article_id: i64,
+    media_ids: Vec<Uuid>,
+) -> Result<()> {
     sql_query(
         r#"
-        WITH deleted_article AS (
-            DELETE FROM articles
-            WHERE articles.id = $1 AND articles.author_id = $2
-            RETURNING id
-        ),
-        tags_to_delete AS (
-            DELETE FROM articles_tags
+        WITH deleted_article_media AS (
+            DELETE FROM articles_media
             WHERE article_id = $1
-            RETURNING tag_id
-        ),
-        deleted_tags AS (
-             DELETE FROM tags
-             WHERE tags.id IN (SELECT tag_id FROM tags_to_delete)
-                 AND NOT EXISTS (
-                     SELECT 1 FROM articles_tags
-                     WHERE articles_tags.tag_id = tags.id AND articles_tags.article_id != $1
-                 )
-             RETURNING id
         )
-        SELECT 1;
+        INSERT INTO articles_media (article_id, media_id)
+        SELECT $1, media.id
+        FROM media
+        WHERE media.public_id = ANY($2)
+            AND media.owner_id IN (
+                SELECT user_id FROM article_contributors
+                WHERE article_id = $1 AND accepted_at IS NOT NULL
+            )
+        ON CONFLICT DO NOTHING
         "#,
     )
-    .bind::<Integer, _>(article_id)
-    .bind::<Integer, _>(author_id)
+    .bind::<BigInt, _>(article_id)
+    .bind::<Array<SqlUuid>, _>(media_ids)
     .execute(conn)?;
 
     Ok(())
 }
 
-pub fn get_article(db_pool: &DbPool, article_id: i32) -> Result<ArticleEntry> {
+fn find_owned_media(conn: &mut PgConnection, owner_id: i64, media_id: Uuid) -> Result<i64> {
+    let media = sql_query(r#"SELECT id FROM media WHERE public_id = $1 AND owner_id = $2"#)
+        .bind::<SqlUuid, _>(media_id)
+        .bind::<BigInt, _>(owner_id)
+        .get_result::<MediaId>(conn)
+        .optional()?
+        .ok_or(ArticleError::MediaNotFound)?;
+
+    Ok(media.id)
+}
+
+/// Finds an upload of one of the accepted contributors of the article, e.g. a cover picked by a
+/// co-author among the photographer's uploads.
+fn find_contributor_media(conn: &mut PgConnection, article_id: i64, media_id: Uuid) -> Result<i64> {
+    let media = sql_query(
+        r#"
+        SELECT media.id FROM media
+            JOIN article_contributors ON article_contributors.user_id = media.owner_id
+        WHERE media.public_id = $2
+            AND article_contributors.article_id = $1
+            AND article_contributors.accepted_at IS NOT NULL
+    "#,
+    )
+    .bind::<BigInt, _>(article_id)
+    .bind::<SqlUuid, _>(media_id)
+    .get_result::<MediaId>(conn)
+    .optional()?
+    .ok_or(ArticleError::MediaNotFound)?;
+
+    Ok(media.id)
+}
+
+/// Fields of an upload stored by [`create_media`].
+pub struct NewMedia<'a> {
+    pub public_id: Uuid,
+    pub file_name: &'a str,
+    pub content_type: &'a str,
+    pub size_bytes: i64,
+    pub width: i32,
+    pub height: i32,
+    pub storage_key: &'a str,
+    pub thumbnail_key: &'a str,
+}
+
+pub fn create_media(db_pool: &DbPool, owner_id: i64, media: NewMedia) -> Result<MediaEntry> {
     let conn = &mut db_pool.get_connection()?;
 
-    let article = sql_query(
+    let media = sql_query(
+        r#"
+        INSERT INTO media (
+            public_id, owner_id, file_name, content_type, size_bytes, width, height, storage_key,
+            thumbnail_key
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING
+            id, public_id, file_name, content_type, size_bytes, width, height, storage_key,
+            thumbnail_key, created_at
+    "#,
+    )
+    .bind::<SqlUuid, _>(media.public_id)
+    .bind::<BigInt, _>(owner_id)
+    .bind::<Text, _>(media.file_name)
+    .bind::<Text, _>(media.content_type)
+    .bind::<BigInt, _>(media.size_bytes)
+    .bind::<Integer, _>(media.width)
+    .bind::<Integer, _>(media.height)
+    .bind::<Text, _>(media.storage_key)
+    .bind::<Text, _>(media.thumbnail_key)
+    .get_result::<MediaEntry>(conn)?;
+
+    Ok(media)
+}
+
+/// Loads media the viewer uploaded or sees through an article using it as cover or inline.
+/// Hidden articles lend their media only to their author, contributors and `include_hidden`.
+pub fn get_media(
+    db_pool: &DbPool,
+    media_id: Uuid,
+    viewer_id: Option<i64>,
+    include_hidden: bool,
+) -> Result<MediaEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let media = sql_query(format!(
         r#"
         SELECT
-            articles.id,
-            articles.author_id,
-            articles.title,
-            articles.content,
-            articles.created_at,
-            array_agg(tags.name) AS tags,
-            users.username AS author_username
-        FROM articles
-            LEFT JOIN articles_tags ON articles.id = articles_tags.article_id
-            LEFT JOIN tags ON tags.id = articles_tags.tag_id
-            LEFT JOIN users ON users.id = articles.author_id
-        WHERE articles.id = $1
-        GROUP BY articles.id, users.username
-        LIMIT 1
+            id, public_id, file_name, content_type, size_bytes, width, height, storage_key,
+            thumbnail_key, created_at
+        FROM media
+        WHERE public_id = $1
+            AND (
+                media.owner_id = $2
+                OR EXISTS (
+                    SELECT 1 FROM articles
+                    WHERE articles.id IN (
+                        SELECT articles_media.article_id FROM articles_media
+                        WHERE articles_media.media_id = media.id
+                        UNION
+                        SELECT covered.id FROM articles AS covered
+                        WHERE covered.cover_media_id = media.id
+                    )
+                        AND articles.deleted_at IS NULL
+                        AND (
+                            (
+                                articles.status = 'published'
+                                AND (articles.hidden_at IS NULL OR $3)
+                            )
+                            OR {contributed}
+                        )
+                )
+            )
+    "#,
+        contributed = contributed_by("$2"),
+    ))
+    .bind::<SqlUuid, _>(media_id)
+    .bind::<Nullable<BigInt>, _>(viewer_id)
+    .bind::<Bool, _>(include_hidden)
+    .get_result::<MediaEntry>(conn)?;
+
+    Ok(media)
+}
+
+/// Removes media uploaded before `created_before` that no article uses as cover or inline, nor
+/// any of their revisions, and returns their storage keys so the objects can be deleted.
+pub fn delete_orphaned_media(
+    db_pool: &DbPool,
+    created_before: NaiveDateTime,
+) -> Result<Vec<MediaKeysEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let deleted = sql_query(
+        r#"
+        DELETE FROM media
+        WHERE created_at < $1
+            AND NOT EXISTS (SELECT 1 FROM articles WHERE articles.cover_media_id = media.id)
+            AND NOT EXISTS (SELECT 1 FROM articles_media WHERE articles_media.media_id = media.id)
+            AND NOT EXISTS (
+                SELECT 1 FROM article_revisions
+                WHERE article_revisions.media_ids @> ARRAY[media.public_id]
+            )
+        RETURNING storage_key, thumbnail_key
     "#,
     )
-    .bind::<Integer, _>(article_id)
-    .get_result::<ArticleEntry>(conn)?;
+    .bind::<Timestamp, _>(created_before)
+    .load::<MediaKeysEntry>(conn)?;
 
-    Ok(article)
+    Ok(deleted)
+}
+
+/// Permanently removes articles trashed before `deleted_before` together with their tag links,
+/// orphaned tags, and (through cascades) revisions, likes and comments.
+pub fn purge_deleted_articles(db_pool: &DbPool, deleted_before: NaiveDateTime) -> Result<usize> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let purged = sql_query(
+        r#"
+        WITH purged_articles AS (
+            SELECT id FROM articles
+            WHERE deleted_at < $1
+        ),
+        deleted_article_tags AS (
+            DELETE FROM articles_tags
+            WHERE article_id IN (SELECT id FROM purged_articles)
+            RETURNING tag_id
+        ),
+        deleted_articles AS (
+            DELETE FROM articles
+            WHERE id IN (SELECT id FROM purged_articles)
+            RETURNING id
+        ),
+        deleted_tags AS (
+            DELETE FROM tags
+            WHERE tags.id IN (SELECT tag_id FROM deleted_article_tags)
+                AND NOT EXISTS (
+                    SELECT 1 FROM articles_tags
+                    WHERE articles_tags.tag_id = tags.id
+                        AND articles_tags.article_id NOT IN (SELECT id FROM purged_articles)
+                )
+        )
+        SELECT id FROM deleted_articles;
+    "#,
+    )
+    .bind::<Timestamp, _>(deleted_before)
+    .execute(conn)?;
+
+    Ok(purged)
+}
+
+/// Who may write an article: the author alone, or also the contributors editing it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ArticleAccess {
+    Author,
+    /// Accepted co-authors and editors, the author included.
+    Editor,
+}
+
+/// Locks the article row for the rest of the transaction and checks that it exists, the user
+/// has the access and the article is still at the version the caller expects, if any.
+fn lock_article_for_write(
+    conn: &mut PgConnection,
+    user_id: i64,
+    article_id: i64,
+    expected_version: Option<i32>,
+    access: ArticleAccess,
+) -> Result<ArticleAccessEntry> {
+    let current = lock_article_access(conn, user_id, article_id, access)?;
+
+    if expected_version.is_some_and(|expected_version| expected_version != current.version) {
+        return Err(ArticleError::VersionMismatch {
+            current_version: current.version,
+        }
+        .into());
+    }
+
+    Ok(current)
+}
+
+/// Locks the article row for the rest of the transaction and checks that it exists and the user
+/// has the access.
+fn lock_article_access(
+    conn: &mut PgConnection,
+    user_id: i64,
+    article_id: i64,
+    access: ArticleAccess,
+) -> Result<ArticleAccessEntry> {
+    let current = sql_query(
+        r#"
+        SELECT
+            articles.author_id,
+            articles.version,
+            EXISTS (
+                SELECT 1 FROM article_contributors
+                WHERE article_contributors.article_id = articles.id
+                    AND article_contributors.user_id = $2
+                    AND article_contributors.accepted_at IS NOT NULL
+                    AND article_contributors.role IN ('author', 'co_author', 'editor')
+            ) AS is_editor,
+            articles.translation_group_id
+        FROM articles
+        WHERE articles.id = $1 AND articles.deleted_at IS NULL
+        FOR UPDATE OF articles
+    "#,
+    )
+    .bind::<BigInt, _>(article_id)
+    .bind::<BigInt, _>(user_id)
+    .get_result::<ArticleAccessEntry>(conn)
+    .optional()?
+    .ok_or(ArticleError::NotFound)?;
+
+    let permitted = match access {
+        ArticleAccess::Author => current.author_id == user_id,
+        ArticleAccess::Editor => current.author_id == user_id || current.is_editor,
+    };
+    if !permitted {
+        return Err(ArticleError::PermissionDenied.into());
+    }
+
+    Ok(current)
+}
+
+/// Checks that no other article of the translation group is written in the language.
+fn ensure_translation_free(
+    conn: &mut PgConnection,
+    translation_group_id: Uuid,
+    language: &str,
+    article_id: Option<i64>,
+) -> Result<()> {
+    let taken = sql_query(
+        r#"
+        SELECT 1 FROM articles
+        WHERE translation_group_id = $1
+            AND language = $2
+            AND id IS DISTINCT FROM $3
+            AND deleted_at IS NULL
+    "#,
+    )
+    .bind::<SqlUuid, _>(translation_group_id)
+    .bind::<Text, _>(language)
+    .bind::<Nullable<BigInt>, _>(article_id)
+    .execute(conn)?;
+
+    match taken {
+        0 => Ok(()),
+        _ => Err(ArticleError::TranslationExists.into()),
+    }
+}
+
+pub fn get_article_by_slug(
+    db_pool: &DbPool,
+    slug: &str,
+    viewer_id: Option<i64>,
+    include_hidden: bool,
+) -> Result<(ArticleEntry, bool)> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let slug_entry = sql_query(r#"SELECT article_id, slug FROM article_slugs WHERE slug = $1"#)
+        .bind::<Text, _>(slug)
+        .get_result::<ArticleSlugEntry>(conn)?;
+
+    let article = find_article(
+        conn,
+        slug_entry.article_id,
+        viewer_id,
+        include_hidden,
+    )?;
+    let redirected = article.slug != slug_entry.slug;
+
+    Ok((article, redirected))
+}
+
+/// Maps a public article id to the internal key, trashed articles included.
+pub fn get_article_key(db_pool: &DbPool, public_id: Uuid) -> Result<i64> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let article_id = sql_query(r#"SELECT id, public_id FROM articles WHERE public_id = $1"#)
+        .bind::<SqlUuid, _>(public_id)
+        .get_result::<ArticleId>(conn)
+        .optional()?
+        .ok_or(ArticleError::NotFound)?;
+
+    Ok(article_id.id)
+}
+
+/// Picks the translation of an article in the first of the preferred languages it is available
+/// in to the viewer, the article itself when there is none.
+pub fn resolve_translation(
+    db_pool: &DbPool,
+    article_id: i64,
+    languages: &[String],
+    viewer_id: Option<i64>,
+    include_hidden: bool,
+) -> Result<i64> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let translation = sql_query(format!(
+        r#"
+        SELECT articles.id, articles.public_id
+        FROM articles AS source
+            JOIN articles ON articles.translation_group_id = source.translation_group_id
+        WHERE source.id = $1
+            AND articles.language = ANY($2)
+            AND articles.deleted_at IS NULL
+            AND {visible}
+            AND {shown}
+        ORDER BY array_position($2, articles.language::TEXT)
+        LIMIT 1
+    "#,
+        visible = visible_to("$3"),
+        shown = shown_to("$3", "$4"),
+    ))
+    .bind::<BigInt, _>(article_id)
+    .bind::<Array<Text>, _>(languages)
+    .bind::<Nullable<BigInt>, _>(viewer_id)
+    .bind::<Bool, _>(include_hidden)
+    .get_result::<ArticleId>(conn)
+    .optional()?;
+
+    Ok(translation.map_or(article_id, |translation| translation.id))
+}
+
+/// Loads an article visible to the viewer, articles hidden by moderators only to their author and
+/// contributors or when `include_hidden`.
+pub fn get_article(
+    db_pool: &DbPool,
+    article_id: i64,
+    viewer_id: Option<i64>,
+    include_hidden: bool,
+) -> Result<ArticleEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let article = find_article(conn, article_id, viewer_id, include_hidden)?;
+
+    Ok(article)
+}
+
+/// Loads an article visible to the viewer, hidden ones only to its author and contributors or
+/// when `include_hidden`.
+fn find_article(
+    conn: &mut PgConnection,
+    article_id: i64,
+    viewer_id: Option<i64>,
+    include_hidden: bool,
+) -> QueryResult<ArticleEntry> {
+    sql_query(format!(
+        r#"
+        SELECT
+            {ARTICLE_COLUMNS},
+            {ARTICLE_CONTENT_COLUMNS},
+            EXISTS (
+                SELECT 1 FROM bookmarks
+                WHERE bookmarks.article_id = articles.id AND bookmarks.user_id = $2
+            ) AS bookmarked_by_me
+        FROM articles
+            {ARTICLE_JOINS}
+        WHERE articles.id = $1
+            AND articles.deleted_at IS NULL
+            AND {visible}
+            AND {shown}
+    "#,
+        visible = visible_to("$2"),
+        shown = shown_to("$2", "$3"),
+    ))
+    .bind::<BigInt, _>(article_id)
+    .bind::<Nullable<BigInt>, _>(viewer_id)
+    .bind::<Bool, _>(include_hidden)
+    .get_result::<ArticleEntry>(conn)
+}
+
+/// Loads the articles visible to the viewer among the given public and legacy ids, in no
+/// particular order.
+pub fn get_articles_by_ids(
+    db_pool: &DbPool,
+    public_ids: &[Uuid],
+    legacy_ids: &[i64],
+    viewer_id: Option<i64>,
+    include_hidden: bool,
+) -> Result<Vec<ArticleEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let articles = sql_query(format!(
+        r#"
+        SELECT
+            {ARTICLE_COLUMNS},
+            {ARTICLE_CONTENT_COLUMNS},
+            EXISTS (
+                SELECT 1 FROM bookmarks
+                WHERE bookmarks.article_id = articles.id AND bookmarks.user_id = $3
+            ) AS bookmarked_by_me
+        FROM articles
+            {ARTICLE_JOINS}
+        WHERE (articles.public_id = ANY($1) OR articles.id = ANY($2))
+            AND articles.deleted_at IS NULL
+            AND {visible}
+            AND {shown}
+    "#,
+        visible = visible_to("$3"),
+        shown = shown_to("$3", "$4"),
+    ))
+    .bind::<Array<SqlUuid>, _>(public_ids)
+    .bind::<Array<BigInt>, _>(legacy_ids)
+    .bind::<Nullable<BigInt>, _>(viewer_id)
+    .bind::<Bool, _>(include_hidden)
+    .load::<ArticleEntry>(conn)?;
+
+    Ok(articles)
+}
+
+pub fn publish_article(
+    db_pool: &DbPool,
+    author_id: i64,
+    article_id: i64,
+    status: &str,
+    publish_at: NaiveDateTime,
+) -> Result<ArticlePublicationEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let publication = sql_query(
+        r#"
+        UPDATE articles
+        SET status = $3, publish_at = $4
+        WHERE id = $1 AND author_id = $2 AND status <> 'published' AND deleted_at IS NULL
+        RETURNING
+            status,
+            publish_at,
+            EXISTS (
+                SELECT 1 FROM articles_tags WHERE articles_tags.article_id = articles.id
+            ) AS tagged
+    "#,
+    )
+    .bind::<BigInt, _>(article_id)
+    .bind::<BigInt, _>(author_id)
+    .bind::<Text, _>(status)
+    .bind::<Timestamp, _>(publish_at)
+    .get_result::<ArticlePublicationEntry>(conn)?;
+
+    Ok(publication)
+}
+
+pub fn unpublish_article(
+    db_pool: &DbPool,
+    author_id: i64,
+    article_id: i64,
+    status: &str,
+) -> Result<ArticlePublicationEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let publication = sql_query(
+        r#"
+        UPDATE articles
+        SET status = $3, publish_at = NULL
+        WHERE id = $1 AND author_id = $2 AND deleted_at IS NULL
+        RETURNING
+            status,
+            publish_at,
+            EXISTS (
+                SELECT 1 FROM articles_tags WHERE articles_tags.article_id = articles.id
+            ) AS tagged
+    "#,
+    )
+    .bind::<BigInt, _>(article_id)
+    .bind::<BigInt, _>(author_id)
+    .bind::<Text, _>(status)
+    .get_result::<ArticlePublicationEntry>(conn)?;
+
+    Ok(publication)
+}
+
+/// Publishes the scheduled articles due by `now`, telling whether any of them has tags.
+pub fn publish_scheduled_articles(db_pool: &DbPool, now: NaiveDateTime) -> Result<bool> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let published = sql_query(
+        r#"
+        UPDATE articles
+        SET status = 'published'
+        WHERE status = 'scheduled' AND publish_at <= $1 AND deleted_at IS NULL
+        RETURNING
+            EXISTS (
+                SELECT 1 FROM articles_tags WHERE articles_tags.article_id = articles.id
+            ) AS tagged
+    "#,
+    )
+    .bind::<Timestamp, _>(now)
+    .load::<ScheduledPublicationEntry>(conn)?;
+
+    Ok(published.iter().any(|publication| publication.tagged))
+}
+
+pub fn get_article_revisions(
+    db_pool: &DbPool,
+    article_id: i64,
+    viewer_id: Option<i64>,
+    include_hidden: bool,
+) -> Result<Vec<ArticleRevisionEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let revisions = sql_query(format!(
+        r#"
+        SELECT
+            article_revisions.article_id,
+            articles.public_id AS article_public_id,
+            article_revisions.revision,
+            users.username AS editor_username,
+            article_revisions.title,
+            article_revisions.content,
+            article_revisions.content_format,
+            article_revisions.tags,
+            article_revisions.created_at
+        FROM article_revisions
+            JOIN articles ON articles.id = article_revisions.article_id
+            JOIN users ON users.id = article_revisions.editor_id
+        WHERE article_revisions.article_id = $1
+            AND articles.deleted_at IS NULL
+            AND {visible}
+            AND {shown}
+        ORDER BY article_revisions.revision DESC
+    "#,
+        visible = visible_to("$2"),
+        shown = shown_to("$2", "$3"),
+    ))
+    .bind::<BigInt, _>(article_id)
+    .bind::<Nullable<BigInt>, _>(viewer_id)
+    .bind::<Bool, _>(include_hidden)
+    .load::<ArticleRevisionEntry>(conn)?;
+
+    Ok(revisions)
+}
+
+pub fn get_article_revision(
+    db_pool: &DbPool,
+    article_id: i64,
+    revision: i32,
+    viewer_id: Option<i64>,
+    include_hidden: bool,
+) -> Result<ArticleRevisionEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let revision = sql_query(format!(
+        r#"
+        SELECT
+            article_revisions.article_id,
+            articles.public_id AS article_public_id,
+            article_revisions.revision,
+            users.username AS editor_username,
+            article_revisions.title,
+            article_revisions.content,
+            article_revisions.content_format,
+            article_revisions.tags,
+            article_revisions.created_at
+        FROM article_revisions
+            JOIN articles ON articles.id = article_revisions.article_id
+            JOIN users ON users.id = article_revisions.editor_id
+        WHERE article_revisions.article_id = $1
+            AND article_revisions.revision = $2
+            AND articles.deleted_at IS NULL
+            AND {visible}
+            AND {shown}
+    "#,
+        visible = visible_to("$3"),
+        shown = shown_to("$3", "$4"),
+    ))
+    .bind::<BigInt, _>(article_id)
+    .bind::<Integer, _>(revision)
+    .bind::<Nullable<BigInt>, _>(viewer_id)
+    .bind::<Bool, _>(include_hidden)
+    .get_result::<ArticleRevisionEntry>(conn)?;
+
+    Ok(revision)
+}
+
+/// Counts only published articles, so tags used by drafts or hidden articles alone stay hidden.
+const TAG_COUNT_JOINS: &str = r#"
+    LEFT JOIN articles_tags ON articles_tags.tag_id = tags.id
+    LEFT JOIN articles ON articles.id = articles_tags.article_id
+        AND articles.status = 'published'
+        AND articles.deleted_at IS NULL
+        AND articles.hidden_at IS NULL
+"#;
+
+/// Loads used tags most used first, keyed by `(article_count, name)`. Counts are the ones of the
+/// last refresh, so they hold still while a client pages.
+pub fn get_tags_page(
+    db_pool: &DbPool,
+    cursor: Option<&PageCursor<(i64, String)>>,
+    limit: i64,
+) -> Result<Vec<TagEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let (keyset, order) = match cursor.map(|cursor| cursor.direction) {
+        None => ("", "-article_count, name"),
+        Some(PageDirection::Next) => (
+            "AND (-article_count, name) > (-$2, $3)",
+            "-article_count, name",
+        ),
+        Some(PageDirection::Prev) => (
+            "AND (-article_count, name) < (-$2, $3)",
+            "-article_count DESC, name DESC",
+        ),
+    };
+
+    let mut query = sql_query(format!(
+        r#"
+        SELECT name, article_count
+        FROM tags
+        WHERE article_count > 0
+            {keyset}
+        ORDER BY {order}
+        LIMIT $1
+    "#
+    ))
+    .into_boxed::<Pg>()
+    .bind::<Int8, _>(limit);
+
+    if let Some(cursor) = cursor {
+        let (article_count, name) = cursor.position.clone();
+        query = query.bind::<BigInt, _>(article_count).bind::<Text, _>(name);
+    }
+
+    let tags = query.load::<TagEntry>(conn)?;
+
+    Ok(tags)
+}
+
+/// Recounts the published articles of every tag, see [`refresh_article_scores`].
+pub fn refresh_tag_counts(db_pool: &DbPool) -> Result<usize> {
+    let conn = &mut db_pool.get_connection()?;
+
+    Ok(update_tag_counts(conn)?)
+}
+
+fn update_tag_counts(conn: &mut PgConnection) -> QueryResult<usize> {
+    sql_query(format!(
+        r#"
+        UPDATE tags
+        SET article_count = counts.article_count
+        FROM (
+            SELECT tags.id, COUNT(articles.id) AS article_count
+            FROM tags
+                {TAG_COUNT_JOINS}
+            GROUP BY tags.id
+        ) AS counts
+        WHERE tags.id = counts.id
+            AND tags.article_count <> counts.article_count
+    "#
+    ))
+    .execute(conn)
+}
+
+/// Ranks the published articles related to an article: each shared tag counts 1, the trigram
+/// similarity of the titles up to 2 and the text rank against the words of its title up to about
+/// 1. Its translations are left out.
+pub fn get_related_article_ids(db_pool: &DbPool, article_id: i64, limit: i64) -> Result<Vec<i64>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    // Candidates sharing a tag, matching the title words or with a similar title are each found
+    // through their own index, an OR across them would scan every article.
+    let related = sql_query(
+        r#"
+        WITH source AS (
+            SELECT
+                id,
+                title,
+                translation_group_id,
+                NULLIF(
+                    replace(plainto_tsquery(search_config, title)::TEXT, ' & ', ' | '),
+                    ''
+                )::TSQUERY AS query
+            FROM articles
+            WHERE id = $1
+        ),
+        source_tags AS (
+            SELECT tag_id FROM articles_tags WHERE article_id = $1
+        ),
+        candidate_ids AS (
+            SELECT articles_tags.article_id AS id
+            FROM articles_tags
+            WHERE articles_tags.tag_id IN (SELECT tag_id FROM source_tags)
+            UNION
+            SELECT articles.id
+            FROM articles, source
+            WHERE articles.search_vector @@ source.query
+            UNION
+            SELECT articles.id
+            FROM articles, source
+            WHERE articles.title % source.title
+        ),
+        candidates AS (
+            SELECT
+                articles.id,
+                articles.public_id,
+                (
+                    SELECT COUNT(*) FROM articles_tags
+                    WHERE articles_tags.article_id = articles.id
+                        AND articles_tags.tag_id IN (SELECT tag_id FROM source_tags)
+                ) AS shared_tags,
+                similarity(articles.title, source.title) AS title_similarity,
+                COALESCE(ts_rank(articles.search_vector, source.query), 0) AS text_rank
+            FROM candidate_ids
+                JOIN articles ON articles.id = candidate_ids.id,
+                source
+            WHERE articles.translation_group_id <> source.translation_group_id
+                AND articles.status = 'published'
+                AND articles.deleted_at IS NULL
+                AND articles.hidden_at IS NULL
+        )
+        SELECT id, public_id
+        FROM candidates
+        ORDER BY shared_tags + 2 * title_similarity + 10 * text_rank DESC, id DESC
+        LIMIT $2
+    "#,
+    )
+    .bind::<BigInt, _>(article_id)
+    .bind::<Int8, _>(limit)
+    .load::<ArticleId>(conn)?;
+
+    Ok(related.into_iter().map(|article| article.id).collect())
+}
+
+/// Suggests used tags starting with the query or similar to it by trigrams, both found through
+/// the name indexes. Prefix matches come first, the rest closest by edit distance (typos). Counts
+/// are the ones of the last refresh.
+pub fn suggest_tags(db_pool: &DbPool, query: &str, limit: i64) -> Result<Vec<TagEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let tags = sql_query(
+        r#"
+        SELECT name, article_count
+        FROM tags
+        WHERE article_count > 0
+            AND (name LIKE $2 OR name % $1)
+        ORDER BY
+            name LIKE $2 DESC,
+            levenshtein_less_equal(name, $1, 3),
+            similarity(name, $1) DESC,
+            article_count DESC
+        LIMIT $3
+    "#,
+    )
+    .bind::<Text, _>(query)
+    .bind::<Text, _>(format!("{}%", escape_like(query)))
+    .bind::<Int8, _>(limit)
+    .load::<TagEntry>(conn)?;
+
+    Ok(tags)
+}
+
+pub fn rename_tag(db_pool: &DbPool, name: &str, new_name: &str) -> Result<TagEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let renamed = sql_query(
+            r#"
+            UPDATE tags SET name = $2
+            WHERE name = $1 AND NOT EXISTS (SELECT 1 FROM tags WHERE name = $2)
+            RETURNING id
+        "#,
+        )
+        .bind::<Text, _>(name)
+        .bind::<Text, _>(new_name)
+        .get_result::<TagId>(conn)
+        .optional()?;
+
+        if renamed.is_none() {
+            return match find_tag(conn, name)?.is_some() {
+                true => Err(TagError::AlreadyExists.into()),
+                false => Err(TagError::NotFound.into()),
+            };
+        }
+
+        let tag = find_tag(conn, new_name)?.ok_or(TagError::NotFound)?;
+
+        Ok(tag)
+    })
+}
+
+/// Moves the articles of the source tags to the target tag (created when missing) and deletes
+/// the source tags.
+pub fn merge_tags(
+    db_pool: &DbPool,
+    source_names: Vec<String>,
+    target_name: &str,
+) -> Result<TagEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let sources = sql_query(r#"SELECT id FROM tags WHERE name = ANY($1) AND name <> $2"#)
+            .bind::<Array<Text>, _>(source_names)
+            .bind::<Text, _>(target_name)
+            .load::<TagId>(conn)?
+            .into_iter()
+            .map(|tag| tag.id)
+            .collect::<Vec<_>>();
+
+        if sources.is_empty() {
+            return Err(TagError::NotFound.into());
+        }
+
+        sql_query(
+            r#"
+            WITH target_tag AS (
+                INSERT INTO tags (name)
+                VALUES ($2)
+                ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+                RETURNING id
+            ),
+            moved_article_tags AS (
+                INSERT INTO articles_tags (article_id, tag_id)
+                SELECT DISTINCT articles_tags.article_id, target_tag.id
+                FROM articles_tags, target_tag
+                WHERE articles_tags.tag_id = ANY($1)
+                ON CONFLICT DO NOTHING
+            ),
+            deleted_article_tags AS (
+                DELETE FROM articles_tags
+                WHERE tag_id = ANY($1)
+            )
+            SELECT 1
+        "#,
+        )
+        .bind::<Array<BigInt>, _>(&sources)
+        .bind::<Text, _>(target_name)
+        .execute(conn)?;
+
+        sql_query(r#"DELETE FROM tags WHERE id = ANY($1)"#)
+            .bind::<Array<BigInt>, _>(&sources)
+            .execute(conn)?;
+        // The target is listed with the articles it took over right away.
+        update_tag_counts(conn)?;
+
+        let tag = find_tag(conn, target_name)?.ok_or(TagError::NotFound)?;
+
+        Ok(tag)
+    })
+}
+
+fn find_tag(conn: &mut PgConnection, name: &str) -> QueryResult<Option<TagEntry>> {
+    sql_query(format!(
+        r#"
+        SELECT tags.name, COUNT(articles.id) AS article_count
+        FROM tags
+            {TAG_COUNT_JOINS}
+        WHERE tags.name = $1
+        GROUP BY tags.id
+    "#
+    ))
+    .bind::<Text, _>(name)
+    .get_result::<TagEntry>(conn)
+    .optional()
+}
+
+/// Bookmarks an article visible to the user, bookmarking it again moves it to the given reading
+/// list (or out of any).
+pub fn add_bookmark(
+    db_pool: &DbPool,
+    user_id: i64,
+    article_id: i64,
+    reading_list_id: Option<Uuid>,
+    include_hidden: bool,
+) -> Result<BookmarkEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let reading_list_id = reading_list_id
+            .map(|reading_list_id| find_reading_list(conn, user_id, reading_list_id))
+            .transpose()?;
+
+        let bookmark = sql_query(format!(
+            r#"
+            WITH bookmark AS (
+                INSERT INTO bookmarks (user_id, article_id, reading_list_id)
+                SELECT $1, articles.id, $3
+                FROM articles
+                WHERE articles.id = $2
+                    AND articles.deleted_at IS NULL
+                    AND {visible}
+                    AND {shown}
+                ON CONFLICT (user_id, article_id)
+                    DO UPDATE SET reading_list_id = EXCLUDED.reading_list_id
+                RETURNING article_id, reading_list_id, created_at
+            )
+            SELECT
+                articles.public_id AS article_public_id,
+                reading_lists.public_id AS reading_list_public_id,
+                bookmark.created_at AS bookmarked_at
+            FROM bookmark
+                JOIN articles ON articles.id = bookmark.article_id
+                LEFT JOIN reading_lists ON reading_lists.id = bookmark.reading_list_id
+        "#,
+            visible = visible_to("$1"),
+            shown = shown_to("$1", "$4"),
+        ))
+        .bind::<BigInt, _>(user_id)
+        .bind::<BigInt, _>(article_id)
+        .bind::<Nullable<BigInt>, _>(reading_list_id)
+        .bind::<Bool, _>(include_hidden)
+        .get_result::<BookmarkEntry>(conn)
+        .optional()?
+        .ok_or(ArticleError::NotFound)?;
+
+        Ok(bookmark)
+    })
+}
+
+pub fn remove_bookmark(db_pool: &DbPool, user_id: i64, article_id: i64) -> Result<()> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let removed = sql_query(r#"DELETE FROM bookmarks WHERE user_id = $1 AND article_id = $2"#)
+        .bind::<BigInt, _>(user_id)
+        .bind::<BigInt, _>(article_id)
+        .execute(conn)?;
+
+    if removed == 0 {
+        return Err(BookmarkError::NotFound.into());
+    }
+
+    Ok(())
+}
+
+/// Loads bookmarks of the user most recent first, keyed by `(created_at, bookmark_id)`. Articles
+/// trashed, unpublished or hidden since stay bookmarked but are left out, hidden ones unless
+/// `include_hidden`.
+pub fn get_bookmarks_page(
+    db_pool: &DbPool,
+    user_id: i64,
+    reading_list_id: Option<Uuid>,
+    cursor: Option<&PageCursor<(NaiveDateTime, i64)>>,
+    limit: i64,
+    include_hidden: bool,
+) -> Result<Vec<BookmarkPageEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let reading_list_id = reading_list_id
+            .map(|reading_list_id| find_reading_list(conn, user_id, reading_list_id))
+            .transpose()?;
+
+        // Only the requested conditions end up in the query, as in `get_articles_page`.
+        let mut conditions = String::new();
+        let direction = match cursor.map(|cursor| cursor.direction) {
+            None => "DESC",
+            Some(PageDirection::Next) => {
+                conditions.push_str(
+                    "\n                AND (bookmarks.created_at, bookmarks.id) < ($4, $5)",
+                );
+                "DESC"
+            }
+            Some(PageDirection::Prev) => {
+                conditions.push_str(
+                    "\n                AND (bookmarks.created_at, bookmarks.id) > ($4, $5)",
+                );
+                "ASC"
+            }
+        };
+        if reading_list_id.is_some() {
+            let placeholder = match cursor {
+                Some(_) => 6,
+                None => 4,
+            };
+            conditions.push_str(&format!(
+                "\n                AND bookmarks.reading_list_id = ${placeholder}"
+            ));
+        }
+
+        let mut query = sql_query(format!(
+            r#"
+            SELECT
+                {ARTICLE_COLUMNS},
+                TRUE AS bookmarked_by_me,
+                bookmarks.id AS bookmark_id,
+                articles.public_id AS article_public_id,
+                reading_lists.public_id AS reading_list_public_id,
+                bookmarks.created_at AS bookmarked_at
+            FROM bookmarks
+                JOIN articles ON articles.id = bookmarks.article_id
+                {ARTICLE_JOINS}
+                LEFT JOIN reading_lists ON reading_lists.id = bookmarks.reading_list_id
+            WHERE bookmarks.user_id = $1
+                AND articles.deleted_at IS NULL
+                AND {visible}
+                AND {shown}{conditions}
+            ORDER BY bookmarks.created_at {direction}, bookmarks.id {direction}
+            LIMIT $2
+        "#,
+            visible = visible_to("$1"),
+            shown = shown_to("$1", "$3"),
+        ))
+        .into_boxed::<Pg>()
+        .bind::<BigInt, _>(user_id)
+        .bind::<Int8, _>(limit)
+        .bind::<Bool, _>(include_hidden);
+
+        if let Some(cursor) = cursor {
+            let (created_at, bookmark_id) = cursor.position;
+            query = query
+                .bind::<Timestamp, _>(created_at)
+                .bind::<BigInt, _>(bookmark_id);
+        }
+        if let Some(reading_list_id) = reading_list_id {
+            query = query.bind::<BigInt, _>(reading_list_id);
+        }
+
+        let bookmarks = query.load::<BookmarkPageEntry>(conn)?;
+
+        Ok(bookmarks)
+    })
+}
+
+/// Creates a reading list of the user, placed after the existing ones.
+pub fn create_reading_list(db_pool: &DbPool, user_id: i64, name: &str) -> Result<ReadingListEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        // Lists created at the same time would take the same position, the user row serializes
+        // them even while the user has no list to lock yet.
+        sql_query(r#"SELECT id FROM users WHERE id = $1 FOR NO KEY UPDATE"#)
+            .bind::<BigInt, _>(user_id)
+            .execute(conn)?;
+
+        let reading_list = sql_query(
+            r#"
+            INSERT INTO reading_lists (public_id, user_id, name, position)
+            SELECT $1, $2, $3, COALESCE(MAX(position), 0) + 1
+            FROM reading_lists
+            WHERE user_id = $2
+            ON CONFLICT (user_id, name) DO NOTHING
+            RETURNING public_id, name, 0::BIGINT AS bookmark_count, created_at
+        "#,
+        )
+        .bind::<SqlUuid, _>(Uuid::now_v7())
+        .bind::<BigInt, _>(user_id)
+        .bind::<Text, _>(name)
+        .get_result::<ReadingListEntry>(conn)
+        .optional()?
+        .ok_or(BookmarkError::ReadingListExists)?;
+
+        Ok(reading_list)
+    })
+}
+
+pub fn get_reading_lists(db_pool: &DbPool, user_id: i64) -> Result<Vec<ReadingListEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let reading_lists = find_reading_lists(conn, user_id)?;
+
+    Ok(reading_lists)
+}
+
+/// Reorders the reading lists of the user, the order has to name each of them once.
+pub fn reorder_reading_lists(
+    db_pool: &DbPool,
+    user_id: i64,
+    reading_list_ids: &[Uuid],
+) -> Result<Vec<ReadingListEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let reordered = sql_query(
+            r#"
+            UPDATE reading_lists SET position = ordered.position
+            FROM unnest($2::UUID[]) WITH ORDINALITY AS ordered(public_id, position)
+            WHERE reading_lists.user_id = $1 AND reading_lists.public_id = ordered.public_id
+        "#,
+        )
+        .bind::<BigInt, _>(user_id)
+        .bind::<Array<SqlUuid>, _>(reading_list_ids)
+        .execute(conn)?;
+
+        let reading_lists = find_reading_lists(conn, user_id)?;
+
+        // Fewer updated rows than ids means duplicates or lists of someone else.
+        if reordered != reading_list_ids.len() || reordered != reading_lists.len() {
+            return Err(BookmarkError::ReadingListOrderMismatch.into());
+        }
+
+        Ok(reading_lists)
+    })
+}
+
+/// Deletes a reading list of the user, its bookmarks are kept outside of any list.
+pub fn delete_reading_list(db_pool: &DbPool, user_id: i64, reading_list_id: Uuid) -> Result<()> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let deleted = sql_query(r#"DELETE FROM reading_lists WHERE user_id = $1 AND public_id = $2"#)
+        .bind::<BigInt, _>(user_id)
+        .bind::<SqlUuid, _>(reading_list_id)
+        .execute(conn)?;
+
+    if deleted == 0 {
+        return Err(BookmarkError::ReadingListNotFound.into());
+    }
+
+    Ok(())
+}
+
+fn find_reading_list(conn: &mut PgConnection, user_id: i64, public_id: Uuid) -> Result<i64> {
+    let reading_list =
+        sql_query(r#"SELECT id FROM reading_lists WHERE user_id = $1 AND public_id = $2"#)
+            .bind::<BigInt, _>(user_id)
+            .bind::<SqlUuid, _>(public_id)
+            .get_result::<ReadingListId>(conn)
+            .optional()?
+            .ok_or(BookmarkError::ReadingListNotFound)?;
+
+    Ok(reading_list.id)
+}
+
+fn find_reading_lists(conn: &mut PgConnection, user_id: i64) -> QueryResult<Vec<ReadingListEntry>> {
+    sql_query(
+        r#"
+        SELECT
+            reading_lists.public_id,
+            reading_lists.name,
+            COUNT(bookmarks.id) AS bookmark_count,
+            reading_lists.created_at
+        FROM reading_lists
+            LEFT JOIN bookmarks ON bookmarks.reading_list_id = reading_lists.id
+        WHERE reading_lists.user_id = $1
+        GROUP BY reading_lists.id
+        ORDER BY reading_lists.position, reading_lists.id
+    "#,
+    )
+    .bind::<BigInt, _>(user_id)
+    .load::<ReadingListEntry>(conn)
+}
+
+/// Invites a user to contribute to an article of the author, nobody is put on the byline before
+/// accepting.
+pub fn invite_contributor(
+    db_pool: &DbPool,
+    author_id: i64,
+    article_id: i64,
+    user_id: Uuid,
+    role: &str,
+) -> Result<ContributorEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        lock_article_owner(conn, author_id, article_id)?;
+
+        let invitee = sql_query(r#"SELECT id FROM users WHERE public_id = $1"#)
+            .bind::<SqlUuid, _>(user_id)
+            .get_result::<UserIdEntry>(conn)
+            .optional()?
+            .ok_or(ContributorError::UserNotFound)?;
+
+        let invited = sql_query(
+            r#"
+            INSERT INTO article_contributors (article_id, user_id, role, position, invited_by)
+            SELECT $1, $2, $3, MAX(position) + 1, $4
+            FROM article_contributors
+            WHERE article_id = $1
+            ON CONFLICT (article_id, user_id) DO NOTHING
+        "#,
+        )
+        .bind::<BigInt, _>(article_id)
+        .bind::<BigInt, _>(invitee.id)
+        .bind::<Text, _>(role)
+        .bind::<BigInt, _>(author_id)
+        .execute(conn)?;
+
+        if invited == 0 {
+            return Err(ContributorError::AlreadyInvited.into());
+        }
+
+        Ok(find_contributor(conn, article_id, invitee.id)?)
+    })
+}
+
+/// Pending invitations of the user to articles not in the trash, newest first.
+pub fn get_contributor_invitations(
+    db_pool: &DbPool,
+    user_id: i64,
+) -> Result<Vec<ContributorInvitationEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let invitations = sql_query(
+        r#"
+        SELECT
+            articles.public_id AS article_public_id,
+            articles.title AS article_title,
+            article_contributors.role,
+            inviters.username AS invited_by_username,
+            article_contributors.created_at
+        FROM article_contributors
+            JOIN articles ON articles.id = article_contributors.article_id
+            LEFT JOIN users AS inviters ON inviters.id = article_contributors.invited_by
+        WHERE article_contributors.user_id = $1
+            AND article_contributors.accepted_at IS NULL
+            AND articles.deleted_at IS NULL
+        ORDER BY article_contributors.created_at DESC
+    "#,
+    )
+    .bind::<BigInt, _>(user_id)
+    .load::<ContributorInvitationEntry>(conn)?;
+
+    Ok(invitations)
+}
+
+/// Accepts the user's invitation to the article, putting them last on the byline.
+pub fn accept_contributor_invitation(
+    db_pool: &DbPool,
+    user_id: i64,
+    article_id: i64,
+) -> Result<ContributorEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let accepted = sql_query(
+            r#"
+            UPDATE article_contributors
+            SET
+                accepted_at = CURRENT_TIMESTAMP,
+                position = (
+                    SELECT MAX(position) + 1 FROM article_contributors
+                    WHERE article_id = $1 AND accepted_at IS NOT NULL
+                )
+            WHERE article_id = $1
+                AND user_id = $2
+                AND accepted_at IS NULL
+                AND article_id IN (SELECT id FROM articles WHERE deleted_at IS NULL)
+        "#,
+        )
+        .bind::<BigInt, _>(article_id)
+        .bind::<BigInt, _>(user_id)
+        .execute(conn)?;
+
+        if accepted == 0 {
+            return Err(ContributorError::InvitationNotFound.into());
+        }
+
+        Ok(find_contributor(conn, article_id, user_id)?)
+    })
+}
+
+pub fn decline_contributor_invitation(
+    db_pool: &DbPool,
+    user_id: i64,
+    article_id: i64,
+) -> Result<()> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let declined = sql_query(
+        r#"
+        DELETE FROM article_contributors
+        WHERE article_id = $1 AND user_id = $2 AND accepted_at IS NULL
+    "#,
+    )
+    .bind::<BigInt, _>(article_id)
+    .bind::<BigInt, _>(user_id)
+    .execute(conn)?;
+
+    if declined == 0 {
+        return Err(ContributorError::InvitationNotFound.into());
+    }
+
+    Ok(())
+}
+
+/// Contributors of an article visible to the viewer in byline order, pending invitations follow
+/// when the viewer is a contributor.
+pub fn get_contributors(
+    db_pool: &DbPool,
+    viewer_id: i64,
+    article_id: i64,
+    include_hidden: bool,
+) -> Result<Vec<ContributorEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    find_article(conn, article_id, Some(viewer_id), include_hidden)
+        .optional()?
+        .ok_or(ArticleError::NotFound)?;
+
+    let contributors = find_contributors(conn, article_id, Some(viewer_id))?;
+
+    Ok(contributors)
+}
+
+/// Takes a contributor off the article or withdraws an invitation. The author can remove anyone
+/// but themselves, other contributors only themselves.
+pub fn remove_contributor(
+    db_pool: &DbPool,
+    user_id: i64,
+    article_id: i64,
+    contributor_id: Uuid,
+) -> Result<()> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let article = lock_article(conn, article_id)?;
+
+        let contributor = sql_query(
+            r#"
+            SELECT users.id FROM article_contributors
+                JOIN users ON users.id = article_contributors.user_id
+            WHERE article_contributors.article_id = $1 AND users.public_id = $2
+        "#,
+        )
+        .bind::<BigInt, _>(article_id)
+        .bind::<SqlUuid, _>(contributor_id)
+        .get_result::<UserIdEntry>(conn)
+        .optional()?
+        .ok_or(ContributorError::NotFound)?;
+
+        if contributor.id == article.author_id {
+            return Err(ContributorError::AuthorRequired.into());
+        }
+        if user_id != article.author_id && user_id != contributor.id {
+            return Err(ArticleError::PermissionDenied.into());
+        }
+
+        sql_query(r#"DELETE FROM article_contributors WHERE article_id = $1 AND user_id = $2"#)
+            .bind::<BigInt, _>(article_id)
+            .bind::<BigInt, _>(contributor.id)
+            .execute(conn)?;
+
+        Ok(())
+    })
+}
+
+/// Reorders the byline of an article of the author, the order has to name each accepted
+/// contributor once.
+pub fn reorder_contributors(
+    db_pool: &DbPool,
+    author_id: i64,
+    article_id: i64,
+    user_ids: &[Uuid],
+) -> Result<Vec<ContributorEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        lock_article_owner(conn, author_id, article_id)?;
+
+        let reordered = sql_query(
+            r#"
+            UPDATE article_contributors SET position = ordered.position
+            FROM unnest($2::UUID[]) WITH ORDINALITY AS ordered(public_id, position)
+                JOIN users ON users.public_id = ordered.public_id
+            WHERE article_contributors.article_id = $1
+                AND article_contributors.user_id = users.id
+                AND article_contributors.accepted_at IS NOT NULL
+        "#,
+        )
+        .bind::<BigInt, _>(article_id)
+        .bind::<Array<SqlUuid>, _>(user_ids)
+        .execute(conn)?;
+
+        let contributors = find_contributors(conn, article_id, None)?;
+
+        // Fewer updated rows than ids means duplicates or users not on the byline.
+        if reordered != user_ids.len() || reordered != contributors.len() {
+            return Err(ContributorError::OrderMismatch.into());
+        }
+
+        Ok(contributors)
+    })
+}
+
+/// Hands an article of the author over to one of its accepted contributors, who becomes its
+/// author while the former author stays on as a co-author.
+pub fn transfer_article_ownership(
+    db_pool: &DbPool,
+    author_id: i64,
+    article_id: i64,
+    new_author_id: Uuid,
+) -> Result<ArticleEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        lock_article_owner(conn, author_id, article_id)?;
+
+        let new_author = sql_query(
+            r#"
+            SELECT users.id FROM article_contributors
+                JOIN users ON users.id = article_contributors.user_id
+            WHERE article_contributors.article_id = $1
+                AND users.public_id = $2
+                AND article_contributors.accepted_at IS NOT NULL
+        "#,
+        )
+        .bind::<BigInt, _>(article_id)
+        .bind::<SqlUuid, _>(new_author_id)
+        .get_result::<UserIdEntry>(conn)
+        .optional()?
+        .ok_or(ContributorError::NotFound)?;
+
+        sql_query(
+            r#"
+            WITH updated_article AS (
+                UPDATE articles SET author_id = $2
+                WHERE id = $1
+            )
+            UPDATE article_contributors
+            SET role = CASE WHEN user_id = $2 THEN 'author' ELSE 'co_author' END
+            WHERE article_id = $1 AND user_id IN ($2, $3)
+        "#,
+        )
+        .bind::<BigInt, _>(article_id)
+        .bind::<BigInt, _>(new_author.id)
+        .bind::<BigInt, _>(author_id)
+        .execute(conn)?;
+
+        let article = find_article(conn, article_id, Some(author_id), false)?;
+
+        Ok(article)
+    })
+}
+
+/// Locks the article row for the rest of the transaction and checks that it is not in the trash.
+fn lock_article(conn: &mut PgConnection, article_id: i64) -> Result<ArticleOwnershipEntry> {
+    let article = sql_query(
+        r#"
+        SELECT author_id, version FROM articles
+        WHERE id = $1 AND deleted_at IS NULL
+        FOR UPDATE
+    "#,
+    )
+    .bind::<BigInt, _>(article_id)
+    .get_result::<ArticleOwnershipEntry>(conn)
+    .optional()?
+    .ok_or(ArticleError::NotFound)?;
+
+    Ok(article)
+}
+
+fn lock_article_owner(conn: &mut PgConnection, author_id: i64, article_id: i64) -> Result<()> {
+    match lock_article(conn, article_id)?.author_id == author_id {
+        true => Ok(()),
+        false => Err(ArticleError::PermissionDenied.into()),
+    }
+}
+
+const CONTRIBUTOR_SELECT: &str = r#"
+    SELECT
+        users.public_id AS user_public_id,
+        users.username,
+        article_contributors.role,
+        article_contributors.created_at,
+        article_contributors.accepted_at
+    FROM article_contributors
+        JOIN users ON users.id = article_contributors.user_id
+"#;
+
+fn find_contributor(
+    conn: &mut PgConnection,
+    article_id: i64,
+    user_id: i64,
+) -> QueryResult<ContributorEntry> {
+    sql_query(format!(
+        r#"
+        {CONTRIBUTOR_SELECT}
+        WHERE article_contributors.article_id = $1 AND article_contributors.user_id = $2
+    "#
+    ))
+    .bind::<BigInt, _>(article_id)
+    .bind::<BigInt, _>(user_id)
+    .get_result::<ContributorEntry>(conn)
+}
+
+/// Accepted contributors in byline order, followed by the pending ones when the viewer is an
+/// accepted contributor.
+fn find_contributors(
+    conn: &mut PgConnection,
+    article_id: i64,
+    viewer_id: Option<i64>,
+) -> QueryResult<Vec<ContributorEntry>> {
+    sql_query(format!(
+        r#"
+        {CONTRIBUTOR_SELECT}
+        WHERE article_contributors.article_id = $1
+            AND (
+                article_contributors.accepted_at IS NOT NULL
+                OR EXISTS (
+                    SELECT 1 FROM article_contributors AS viewers
+                    WHERE viewers.article_id = $1
+                        AND viewers.user_id = $2
+                        AND viewers.accepted_at IS NOT NULL
+                )
+            )
+        ORDER BY
+            article_contributors.accepted_at IS NULL,
+            article_contributors.position,
+            article_contributors.created_at
+    "#
+    ))
+    .bind::<BigInt, _>(article_id)
+    .bind::<Nullable<BigInt>, _>(viewer_id)
+    .load::<ContributorEntry>(conn)
+}
+
+/// Path of the section articles are filed under unless another one is picked.
+const DEFAULT_SECTION_PATH: &str = "general";
+
+/// Ids of a section and its subsections, the root is picked by a `WHERE` on `sections`.
+const SECTION_TREE_IDS: &str = r#"
+                SELECT subsections.id
+                FROM sections
+                    JOIN sections AS subsections ON subsections.path = sections.path
+                        OR subsections.path LIKE sections.path || '/%'
+            "#;
+
+/// Counts published articles filed directly under each section.
+const SECTION_SELECT: &str = r#"
+    SELECT
+        sections.public_id,
+        parent_sections.public_id AS parent_public_id,
+        sections.name,
+        sections.slug,
+        sections.path,
+        COUNT(articles.id) AS article_count
+    FROM sections
+        LEFT JOIN sections AS parent_sections ON parent_sections.id = sections.parent_id
+        LEFT JOIN articles ON articles.section_id = sections.id
+            AND articles.status = 'published'
+            AND articles.deleted_at IS NULL
+            AND articles.hidden_at IS NULL
+"#;
+
+/// Fields of a section created by [`create_section`].
+pub struct NewSection<'a> {
+    pub name: &'a str,
+    pub slug: &'a str,
+    pub parent_id: Option<Uuid>,
+}
+
+/// Loads all sections depth-first, each followed by its subsections.
+pub fn get_sections(db_pool: &DbPool) -> Result<Vec<SectionEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let sections = sql_query(format!(
+        r#"
+        {SECTION_SELECT}
+        GROUP BY sections.id, parent_sections.public_id
+        ORDER BY string_to_array(sections.path, '/')
+    "#
+    ))
+    .load::<SectionEntry>(conn)?;
+
+    Ok(sections)
+}
+
+pub fn create_section(db_pool: &DbPool, section: NewSection) -> Result<SectionEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let parent = section
+            .parent_id
+            .map(|parent_id| find_section(conn, parent_id))
+            .transpose()?;
+        let parent_id = parent.as_ref().map(|parent| parent.id);
+
+        let section_id = sql_query(
+            r#"
+            INSERT INTO sections (public_id, parent_id, name, slug, path)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (path) DO NOTHING
+            RETURNING id
+        "#,
+        )
+        .bind::<SqlUuid, _>(Uuid::now_v7())
+        .bind::<Nullable<BigInt>, _>(parent_id)
+        .bind::<Text, _>(section.name)
+        .bind::<Text, _>(section.slug)
+        .bind::<Text, _>(section_path(parent.as_ref(), section.slug))
+        .get_result::<SectionId>(conn)
+        .optional()?
+        .ok_or(SectionError::AlreadyExists)?;
+
+        let section = find_section_entry(conn, section_id.id)?;
+
+        Ok(section)
+    })
+}
+
+/// Renames a section, a new slug changes the paths of its whole subtree.
+pub fn update_section(
+    db_pool: &DbPool,
+    section_id: Uuid,
+    name: &str,
+    slug: Option<&str>,
+) -> Result<SectionEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let section = find_section(conn, section_id)?;
+        let slug = slug.unwrap_or(&section.slug);
+
+        if slug != section.slug {
+            let path = match section.path.rsplit_once('/') {
+                Some((parent_path, _)) => format!("{parent_path}/{slug}"),
+                None => slug.to_string(),
+            };
+            rehome_section_tree(conn, &section, &path)?;
+        }
+
+        sql_query(r#"UPDATE sections SET name = $2, slug = $3 WHERE id = $1"#)
+            .bind::<BigInt, _>(section.id)
+            .bind::<Text, _>(name)
+            .bind::<Text, _>(slug)
+            .execute(conn)?;
+
+        let section = find_section_entry(conn, section.id)?;
+
+        Ok(section)
+    })
+}
+
+/// Moves a section with its subsections and articles under another parent (or to the top level).
+pub fn move_section(
+    db_pool: &DbPool,
+    section_id: Uuid,
+    parent_id: Option<Uuid>,
+) -> Result<SectionEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let section = find_section(conn, section_id)?;
+        let parent = parent_id
+            .map(|parent_id| find_section(conn, parent_id))
+            .transpose()?;
+
+        if let Some(parent) = &parent {
+            if is_in_section_tree(&parent.path, &section.path) {
+                return Err(SectionError::InvalidParent.into());
+            }
+        }
+
+        let path = section_path(parent.as_ref(), &section.slug);
+        if path != section.path {
+            rehome_section_tree(conn, &section, &path)?;
+        }
+
+        sql_query(r#"UPDATE sections SET parent_id = $2 WHERE id = $1"#)
+            .bind::<BigInt, _>(section.id)
+            .bind::<Nullable<BigInt>, _>(parent.map(|parent| parent.id))
+            .execute(conn)?;
+
+        let section = find_section_entry(conn, section.id)?;
+
+        Ok(section)
+    })
+}
+
+/// Moves the articles and subsections of the source sections to the target and deletes the
+/// source sections.
+pub fn merge_sections(
+    db_pool: &DbPool,
+    source_ids: &[Uuid],
+    target_id: Uuid,
+) -> Result<SectionEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let target = find_section(conn, target_id)?;
+
+        for source_id in source_ids {
+            let source = find_section(conn, *source_id)?;
+
+            if source.path == DEFAULT_SECTION_PATH {
+                return Err(SectionError::DefaultSection.into());
+            }
+            if is_in_section_tree(&target.path, &source.path) {
+                return Err(SectionError::InvalidParent.into());
+            }
+
+            let subsections =
+                sql_query(r#"SELECT id, slug, path FROM sections WHERE parent_id = $1"#)
+                    .bind::<BigInt, _>(source.id)
+                    .load::<SectionKeyEntry>(conn)?;
+
+            for subsection in subsections {
+                let path = section_path(Some(&target), &subsection.slug);
+                rehome_section_tree(conn, &subsection, &path)?;
+            }
+
+            sql_query(
+                r#"
+                WITH moved_sections AS (
+                    UPDATE sections SET parent_id = $2 WHERE parent_id = $1
+                ),
+                moved_articles AS (
+                    UPDATE articles SET section_id = $2 WHERE section_id = $1
+                )
+                SELECT 1
+            "#,
+            )
+            .bind::<BigInt, _>(source.id)
+            .bind::<BigInt, _>(target.id)
+            .execute(conn)?;
+
+            sql_query(r#"DELETE FROM sections WHERE id = $1"#)
+                .bind::<BigInt, _>(source.id)
+                .execute(conn)?;
+        }
+
+        let section = find_section_entry(conn, target.id)?;
+
+        Ok(section)
+    })
+}
+
+/// Rewrites the path of a section and of all its subsections, the new path has to be free.
+fn rehome_section_tree(
+    conn: &mut PgConnection,
+    section: &SectionKeyEntry,
+    path: &str,
+) -> Result<()> {
+    if section.path == DEFAULT_SECTION_PATH {
+        return Err(SectionError::DefaultSection.into());
+    }
+
+    // Subsection paths extend their parent's, so a free path leaves the whole subtree free.
+    let taken = sql_query(r#"SELECT id FROM sections WHERE path = $1"#)
+        .bind::<Text, _>(path)
+        .get_result::<SectionId>(conn)
+        .optional()?;
+    if taken.is_some() {
+        return Err(SectionError::AlreadyExists.into());
+    }
+
+    sql_query(
+        r#"
+        UPDATE sections SET path = $2 || substr(path, length($1) + 1)
+        WHERE path = $1 OR path LIKE $1 || '/%'
+    "#,
+    )
+    .bind::<Text, _>(&section.path)
+    .bind::<Text, _>(path)
+    .execute(conn)?;
+
+    Ok(())
+}
+
+fn section_path(parent: Option<&SectionKeyEntry>, slug: &str) -> String {
+    match parent {
+        Some(parent) => format!("{}/{slug}", parent.path),
+        None => slug.to_string(),
+    }
+}
+
+fn is_in_section_tree(path: &str, root_path: &str) -> bool {
+    path.strip_prefix(root_path)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+fn find_section(conn: &mut PgConnection, public_id: Uuid) -> Result<SectionKeyEntry> {
+    let section = sql_query(r#"SELECT id, slug, path FROM sections WHERE public_id = $1"#)
+        .bind::<SqlUuid, _>(public_id)
+        .get_result::<SectionKeyEntry>(conn)
+        .optional()?
+        .ok_or(SectionError::NotFound)?;
+
+    Ok(section)
+}
+
+fn find_default_section(conn: &mut PgConnection) -> Result<i64> {
+    let section = sql_query(r#"SELECT id FROM sections WHERE path = $1"#)
+        .bind::<Text, _>(DEFAULT_SECTION_PATH)
+        .get_result::<SectionId>(conn)
+        .optional()?
+        .ok_or(SectionError::NotFound)?;
+
+    Ok(section.id)
+}
+
+fn find_section_entry(conn: &mut PgConnection, section_id: i64) -> QueryResult<SectionEntry> {
+    sql_query(format!(
+        r#"
+        {SECTION_SELECT}
+        WHERE sections.id = $1
+        GROUP BY sections.id, parent_sections.public_id
+    "#
+    ))
+    .bind::<BigInt, _>(section_id)
+    .get_result::<SectionEntry>(conn)
+}
+
+/// Hides an article held by the content filter and files it into the moderation queue, once
+/// while it waits there.
+fn hold_article(conn: &mut PgConnection, article_id: i64, rule: &str) -> Result<()> {
+    sql_query(r#"UPDATE articles SET hidden_at = COALESCE(hidden_at, $2) WHERE id = $1"#)
+        .bind::<BigInt, _>(article_id)
+        .bind::<Timestamp, _>(Utc::now().naive_utc())
+        .execute(conn)?;
+
+    sql_query(
+        r#"
+        INSERT INTO reports (public_id, article_id, reason, details, held_by)
+        SELECT $1, $2, 'other', $3, $4
+        WHERE NOT EXISTS (
+            SELECT 1 FROM reports
+            WHERE article_id = $2 AND reporter_id IS NULL AND status = 'open'
+        )
+    "#,
+    )
+    .bind::<SqlUuid, _>(Uuid::now_v7())
+    .bind::<BigInt, _>(article_id)
+    .bind::<Text, _>(format!("Held by the content filter rule {rule}"))
+    .bind::<Text, _>(rule)
+    .execute(conn)?;
+
+    Ok(())
+}
+
+/// Runs the content filter over a new article, or over the changed title and content of
+/// `article_id`, within its write. Returns the rule holding the article for review.
+fn filter_article(
+    conn: &mut PgConnection,
+    rules: &FilterRules,
+    author_id: i64,
+    article_id: Option<i64>,
+    title: Option<&str>,
+    content: Option<&str>,
+) -> Result<Option<String>> {
+    let text = [title, content]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n");
+    let duplicate = match content.filter(|content| !content.trim().is_empty()) {
+        Some(content) if rules.checks_duplicates() => {
+            has_duplicate_article(conn, content, article_id)?
+        }
+        _ => false,
+    };
+    // Only new articles count against the rate limit.
+    let recent_posts = match (rules.rate_limit_window(), article_id) {
+        (Some(window), None) => Some(count_recent_articles(
+            conn,
+            author_id,
+            Utc::now().naive_utc() - window,
+        )?),
+        _ => None,
+    };
+
+    let outcome = rules.evaluate(&Submission {
+        text: &text,
+        duplicate,
+        recent_posts,
+    });
+
+    match outcome.verdict {
+        Verdict::Allow => Ok(None),
+        Verdict::Hold => Ok(outcome.rule),
+        Verdict::Reject => Err(ArticleError::Rejected {
+            rule: outcome.rule.unwrap_or_default(),
+        }
+        .into()),
+    }
+}
+
+/// Counts the articles the author created since, trashed ones included. The author row stays
+/// locked until the write commits, so posts created at the same time count each other.
+fn count_recent_articles(
+    conn: &mut PgConnection,
+    author_id: i64,
+    since: NaiveDateTime,
+) -> Result<i64> {
+    sql_query(r#"SELECT id FROM users WHERE id = $1 FOR NO KEY UPDATE"#)
+        .bind::<BigInt, _>(author_id)
+        .execute(conn)?;
+
+    let count = sql_query(
+        r#"SELECT COUNT(*) AS count FROM articles WHERE author_id = $1 AND created_at >= $2"#,
+    )
+    .bind::<BigInt, _>(author_id)
+    .bind::<Timestamp, _>(since)
+    .get_result::<CountEntry>(conn)?;
+
+    Ok(count.count)
+}
+
+/// Whether another live article has the same content, ignoring case and whitespace.
+fn has_duplicate_article(
+    conn: &mut PgConnection,
+    content: &str,
+    article_id: Option<i64>,
+) -> Result<bool> {
+    let duplicates = sql_query(
+        r#"
+        SELECT 1 FROM articles
+        WHERE content_hash = md5(lower(btrim(regexp_replace($1, '\s+', ' ', 'g'))))
+            AND deleted_at IS NULL
+            AND id IS DISTINCT FROM $2
+        LIMIT 1
+    "#,
+    )
+    .bind::<Text, _>(content)
+    .bind::<Nullable<BigInt>, _>(article_id)
+    .execute(conn)?;
+
+    Ok(duplicates > 0)
+}
+
+/// Reports an article visible to the reporter, one open report per reporter and article.
+pub fn report_article(
+    db_pool: &DbPool,
+    reporter_id: i64,
+    article_id: i64,
+    reason: &str,
+    details: &str,
+) -> Result<ReportEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        find_article(conn, article_id, Some(reporter_id), false)
+            .optional()?
+            .filter(|article| !article.hidden)
+            .ok_or(ArticleError::NotFound)?;
+
+        let report = sql_query(
+            r#"
+            INSERT INTO reports (public_id, reporter_id, article_id, reason, details)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT DO NOTHING
+            RETURNING id
+        "#,
+        )
+        .bind::<SqlUuid, _>(Uuid::now_v7())
+        .bind::<BigInt, _>(reporter_id)
+        .bind::<BigInt, _>(article_id)
+        .bind::<Text, _>(reason)
+        .bind::<Text, _>(details)
+        .get_result::<ReportId>(conn)
+        .optional()?
+        .ok_or(ModerationError::AlreadyReported)?;
+
+        let report = find_report(conn, report.id)?;
+
+        Ok(report)
+    })
+}
+
+/// Reports a comment on an article visible to the reporter, one open report per reporter and
+/// comment.
+pub fn report_comment(
+    db_pool: &DbPool,
+    reporter_id: i64,
+    comment_id: Uuid,
+    reason: &str,
+    details: &str,
+) -> Result<ReportEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let comment = sql_query(
+            r#"
+            SELECT id, article_id FROM comments
+            WHERE public_id = $1 AND hidden_at IS NULL AND deleted_at IS NULL
+        "#,
+        )
+        .bind::<SqlUuid, _>(comment_id)
+        .get_result::<CommentKeyEntry>(conn)
+        .optional()?
+        .ok_or(ModerationError::CommentNotFound)?;
+
+        find_article(conn, comment.article_id, Some(reporter_id), false)
+            .optional()?
+            .filter(|article| !article.hidden)
+            .ok_or(ModerationError::CommentNotFound)?;
+
+        let report = sql_query(
+            r#"
+            INSERT INTO reports (public_id, reporter_id, comment_id, reason, details)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT DO NOTHING
+            RETURNING id
+        "#,
+        )
+        .bind::<SqlUuid, _>(Uuid::now_v7())
+        .bind::<BigInt, _>(reporter_id)
+        .bind::<BigInt, _>(comment.id)
+        .bind::<Text, _>(reason)
+        .bind::<Text, _>(details)
+        .get_result::<ReportId>(conn)
+        .optional()?
+        .ok_or(ModerationError::AlreadyReported)?;
+
+        let report = find_report(conn, report.id)?;
+
+        Ok(report)
+    })
+}
+
+/// Page of the reports in the given status, oldest first.
+pub fn get_reports_page(
+    db_pool: &DbPool,
+    status: &str,
+    cursor: Option<&PageCursor<(NaiveDateTime, i64)>>,
+    limit: i64,
+) -> Result<Vec<ReportEntry>> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let (keyset, direction) = match cursor.map(|cursor| cursor.direction) {
+        None => ("", "ASC"),
+        Some(PageDirection::Next) => (
+            "AND (reports.created_at, reports.id) > ($3, $4)",
+            "ASC",
+        ),
+        Some(PageDirection::Prev) => (
+            "AND (reports.created_at, reports.id) < ($3, $4)",
+            "DESC",
+        ),
+    };
+
+    let mut query = sql_query(format!(
+        r#"
+        {REPORT_SELECT}
+        WHERE reports.status = $1
+            {keyset}
+        ORDER BY reports.created_at {direction}, reports.id {direction}
+        LIMIT $2
+    "#
+    ))
+    .into_boxed::<Pg>()
+    .bind::<Text, _>(status)
+    .bind::<Int8, _>(limit);
+
+    if let Some(cursor) = cursor {
+        let (created_at, report_id) = cursor.position;
+        query = query
+            .bind::<Timestamp, _>(created_at)
+            .bind::<BigInt, _>(report_id);
+    }
+
+    let reports = query.load::<ReportEntry>(conn)?;
+
+    Ok(reports)
+}
+
+/// Acts on an open report and closes it along with the other open reports of the same content,
+/// recording the moderator and their reason.
+pub fn moderate_report(
+    db_pool: &DbPool,
+    moderator_id: i64,
+    report_id: Uuid,
+    action: &str,
+    reason: &str,
+) -> Result<ReportEntry> {
+    let conn = &mut db_pool.get_connection()?;
+
+    conn.transaction(|conn| {
+        let report = sql_query(
+            r#"
+            SELECT id, article_id, comment_id, status FROM reports
+            WHERE public_id = $1
+            FOR UPDATE
+        "#,
+        )
+        .bind::<SqlUuid, _>(report_id)
+        .get_result::<ReportTargetEntry>(conn)
+        .optional()?
+        .ok_or(ModerationError::ReportNotFound)?;
+
+        if report.status != "open" {
+            return Err(ModerationError::AlreadyModerated.into());
+        }
+
+        let now = Utc::now().naive_utc();
+        let effect = match (action, report.article_id) {
+            ("hide", Some(_)) => {
+                "UPDATE articles SET hidden_at = COALESCE(hidden_at, $2) WHERE id = $1"
+            }
+            ("hide", None) => {
+                "UPDATE comments SET hidden_at = COALESCE(hidden_at, $2) WHERE id = $1"
+            }
+            ("delete", Some(_)) => {
+                "UPDATE articles SET deleted_at = $2, deleted_by = $3 WHERE id = $1 AND deleted_at IS NULL"
+            }
+            ("delete", None) => {
+                "UPDATE comments SET deleted_at = COALESCE(deleted_at, $2) WHERE id = $1"
+            }
+            ("approve", Some(_)) => "UPDATE articles SET hidden_at = NULL WHERE id = $1",
+            ("approve", None) => "UPDATE comments SET hidden_at = NULL WHERE id = $1",
+            _ => "",
+        };
+
+        if !effect.is_empty() {
+            sql_query(effect)
+                .bind::<BigInt, _>(report.article_id.or(report.comment_id).unwrap_or_default())
+                .bind::<Timestamp, _>(now)
+                .bind::<BigInt, _>(moderator_id)
+                .execute(conn)?;
+        }
+
+        let moderation_action = sql_query(
+            r#"
+            INSERT INTO moderation_actions
+                (moderator_id, action, article_id, comment_id, target_user_id, reason, created_at)
+            VALUES (
+                $1,
+                $2,
+                $3,
+                $4,
+                COALESCE(
+                    (SELECT author_id FROM articles WHERE id = $3),
+                    (SELECT user_id FROM comments WHERE id = $4)
+                ),
+                $5,
+                $6
+            )
+            RETURNING id
+        "#,
+        )
+        .bind::<BigInt, _>(moderator_id)
+        .bind::<Text, _>(action)
+        .bind::<Nullable<BigInt>, _>(report.article_id)
+        .bind::<Nullable<BigInt>, _>(report.comment_id)
+        .bind::<Text, _>(reason)
+        .bind::<Timestamp, _>(now)
+        .get_result::<ReportId>(conn)?;
+
+        sql_query(
+            r#"
+            UPDATE reports SET status = $4, moderation_action_id = $3
+            WHERE status = 'open'
+                AND (article_id = $1 OR comment_id = $2)
+        "#,
+        )
+        .bind::<Nullable<BigInt>, _>(report.article_id)
+        .bind::<Nullable<BigInt>, _>(report.comment_id)
+        .bind::<BigInt, _>(moderation_action.id)
+        .bind::<Text, _>(match action {
+            "dismiss" | "approve" => "dismissed",
+            _ => "resolved",
+        })
+        .execute(conn)?;
+
+        let report = find_report(conn, report.id)?;
+
+        Ok(report)
+    })
+}
+
+const REPORT_SELECT: &str = r#"
+    SELECT
+        reports.id,
+        reports.public_id,
+        COALESCE(articles.public_id, comment_articles.public_id) AS article_public_id,
+        COALESCE(articles.title, comment_articles.title) AS article_title,
+        comments.public_id AS comment_public_id,
+        comments.content AS comment_content,
+        reports.reason,
+        reports.details,
+        reports.held_by,
+        reports.status,
+        reporters.username AS reporter_username,
+        reports.created_at,
+        moderation_actions.action,
+        moderators.username AS moderator_username,
+        moderation_actions.reason AS moderation_reason,
+        moderation_actions.created_at AS moderated_at
+    FROM reports
+        LEFT JOIN articles ON articles.id = reports.article_id
+        LEFT JOIN comments ON comments.id = reports.comment_id
+        LEFT JOIN articles AS comment_articles ON comment_articles.id = comments.article_id
+        LEFT JOIN users AS reporters ON reporters.id = reports.reporter_id
+        LEFT JOIN moderation_actions ON moderation_actions.id = reports.moderation_action_id
+        LEFT JOIN users AS moderators ON moderators.id = moderation_actions.moderator_id
+"#;
+
+fn find_report(conn: &mut PgConnection, report_id: i64) -> QueryResult<ReportEntry> {
+    sql_query(format!(
+        r#"
+        {REPORT_SELECT}
+        WHERE reports.id = $1
+    "#
+    ))
+    .bind::<BigInt, _>(report_id)
+    .get_result::<ReportEntry>(conn)
+}
+
+pub fn get_user_role(db_pool: &DbPool, user_id: i64) -> Result<String> {
+    let conn = &mut db_pool.get_connection()?;
+
+    let user = sql_query(r#"SELECT role FROM users WHERE id = $1"#)
+        .bind::<BigInt, _>(user_id)
+        .get_result::<UserRoleEntry>(conn)?;
+
+    Ok(user.role)
 }
 
 pub fn create_user(
@@ -224,8 +3540,8 @@ pub fn create_user(
 
     let user_id = sql_query(
         r#"
-        INSERT INTO users (username, email, password_hash, salt)
-        VALUES ($1, NULL, $2, $3)
+        INSERT INTO users (public_id, username, email, password_hash, salt)
+        VALUES ($4, $1, NULL, $2, $3)
         ON CONFLICT (username) DO NOTHING
         RETURNING id;
     "#,
@@ -233,12 +3549,13 @@ pub fn create_user(
     .bind::<Text, _>(username)
     .bind::<Text, _>(hashed_password)
     .bind::<Text, _>(salt)
+    .bind::<SqlUuid, _>(Uuid::now_v7())
     .get_result::<UserIdEntry>(conn)?;
 
     Ok(user_id)
 }
 
-pub fn save_session_id(db_pool: &DbPool, user_id: i32, session_id: &str) -> Result<()> {
+pub fn save_session_id(db_pool: &DbPool, user_id: i64, session_id: &str) -> Result<()> {
     let conn = &mut db_pool.get_connection()?;
 
     sql_query(
@@ -249,7 +3566,7 @@ pub fn save_session_id(db_pool: &DbPool, user_id: i32, session_id: &str) -> Resu
     "#,
     )
     .bind::<Text, _>(session_id)
-    .bind::<Integer, _>(user_id)
+    .bind::<BigInt, _>(user_id)
     .execute(conn)?;
 
     Ok(())
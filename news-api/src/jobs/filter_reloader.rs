@@ -0,0 +1,19 @@
+use crate::app_state::AppState;
+use tokio::time::interval;
+
+/// Periodically picks up changes of the content filter rules file.
+pub async fn run_filter_reloader(app_state: AppState) {
+    let mut ticker = interval(app_state.settings.content_filter.get_reload_interval());
+
+    loop {
+        ticker.tick().await;
+
+        match app_state.content_filter.reload() {
+            Ok(true) => eprintln!("[news-api] [content-filter] reloaded rules"),
+            Ok(false) => {}
+            Err(err) => {
+                eprintln!("[news-api] [content-filter] failed to reload rules: {err:#}")
+            }
+        }
+    }
+}
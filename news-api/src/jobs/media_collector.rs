@@ -0,0 +1,32 @@
+use crate::app_state::AppState;
+use crate::infrastructure::delete_orphaned_media;
+use diesel::internal::derives::multiconnection::chrono::Utc;
+use tokio::time::interval;
+
+/// Periodically deletes uploads that no article references once they are past the retention
+/// period, giving authors time to attach fresh uploads.
+pub async fn run_media_collector(app_state: AppState) {
+    let jobs = &app_state.settings.jobs;
+    let mut ticker = interval(jobs.get_media_gc_interval());
+
+    loop {
+        ticker.tick().await;
+
+        let created_before = Utc::now().naive_utc() - jobs.get_media_orphan_retention();
+        let orphans = match delete_orphaned_media(&app_state.db_pool, created_before) {
+            Ok(orphans) => orphans,
+            Err(err) => {
+                eprintln!("[news-api] [media] failed to delete orphaned media: {err:#}");
+                continue;
+            }
+        };
+
+        for orphan in orphans {
+            for key in [orphan.storage_key, orphan.thumbnail_key] {
+                if let Err(err) = app_state.media_storage.delete(&key).await {
+                    eprintln!("[news-api] [media] failed to delete {key}: {err:#}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::app_state::AppState;
+use crate::infrastructure::publish_scheduled_articles;
+use diesel::internal::derives::multiconnection::chrono::Utc;
+use tokio::time::interval;
+
+/// Periodically flips `scheduled` articles whose `publish_at` has passed to `published`.
+pub async fn run_publish_scheduler(app_state: AppState) {
+    let mut ticker = interval(app_state.settings.jobs.get_publish_interval());
+
+    loop {
+        ticker.tick().await;
+
+        let now = Utc::now().naive_utc();
+        match publish_scheduled_articles(&app_state.db_pool, now) {
+            // Rankings cached before miss the newly published articles sharing their tags.
+            Ok(true) => app_state.related_cache.clear(),
+            Ok(false) => {}
+            Err(err) => {
+                eprintln!("[news-api] [scheduler] failed to publish scheduled articles: {err:#}")
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::app_state::AppState;
+use crate::infrastructure::{refresh_article_scores, refresh_tag_counts};
+use diesel::internal::derives::multiconnection::chrono::Utc;
+use tokio::time::interval;
+
+/// Periodically recomputes the article scores the top and trending feeds are ordered by, and the
+/// tag counts `ListTags` is ordered by.
+pub async fn run_score_refresher(app_state: AppState) {
+    let mut ticker = interval(app_state.settings.jobs.get_score_interval());
+
+    loop {
+        ticker.tick().await;
+
+        let now = Utc::now().naive_utc();
+        if let Err(err) = refresh_article_scores(&app_state.db_pool, now) {
+            eprintln!("[news-api] [scores] failed to refresh article scores: {err:#}");
+        }
+        if let Err(err) = refresh_tag_counts(&app_state.db_pool) {
+            eprintln!("[news-api] [scores] failed to refresh tag counts: {err:#}");
+        }
+    }
+}
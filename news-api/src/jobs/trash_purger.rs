@@ -0,0 +1,19 @@
+use crate::app_state::AppState;
+use crate::infrastructure::purge_deleted_articles;
+use diesel::internal::derives::multiconnection::chrono::Utc;
+use tokio::time::interval;
+
+/// Periodically purges articles that stayed in the trash longer than the retention period.
+pub async fn run_trash_purger(app_state: AppState) {
+    let jobs = &app_state.settings.jobs;
+    let mut ticker = interval(jobs.get_purge_interval());
+
+    loop {
+        ticker.tick().await;
+
+        let deleted_before = Utc::now().naive_utc() - jobs.get_trash_retention();
+        if let Err(err) = purge_deleted_articles(&app_state.db_pool, deleted_before) {
+            eprintln!("[news-api] [purger] failed to purge deleted articles: {err:#}");
+        }
+    }
+}
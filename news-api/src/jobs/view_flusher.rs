@@ -0,0 +1,23 @@
+use crate::app_state::AppState;
+use crate::infrastructure::record_article_views;
+use diesel::internal::derives::multiconnection::chrono::Utc;
+use tokio::time::interval;
+
+/// Periodically writes the article views buffered by the view recorder in one batch.
+pub async fn run_view_flusher(app_state: AppState) {
+    let mut ticker = interval(app_state.settings.jobs.get_view_flush_interval());
+
+    loop {
+        ticker.tick().await;
+
+        let buckets = app_state.view_recorder.drain(Utc::now().naive_utc());
+        if buckets.is_empty() {
+            continue;
+        }
+
+        if let Err(err) = record_article_views(&app_state.db_pool, &buckets) {
+            eprintln!("[news-api] [views] failed to flush article views: {err:#}");
+            app_state.view_recorder.restore(buckets);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+/// Language of articles that don't name one.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Postgres text search configurations by the language code they stem.
+const SEARCH_CONFIGS: &[(&str, &str)] = &[
+    ("ar", "arabic"),
+    ("ca", "catalan"),
+    ("da", "danish"),
+    ("de", "german"),
+    ("el", "greek"),
+    ("en", "english"),
+    ("es", "spanish"),
+    ("eu", "basque"),
+    ("fi", "finnish"),
+    ("fr", "french"),
+    ("ga", "irish"),
+    ("hi", "hindi"),
+    ("hu", "hungarian"),
+    ("hy", "armenian"),
+    ("id", "indonesian"),
+    ("it", "italian"),
+    ("lt", "lithuanian"),
+    ("ne", "nepali"),
+    ("nl", "dutch"),
+    ("no", "norwegian"),
+    ("pt", "portuguese"),
+    ("ro", "romanian"),
+    ("ru", "russian"),
+    ("sr", "serbian"),
+    ("sv", "swedish"),
+    ("ta", "tamil"),
+    ("tr", "turkish"),
+    ("yi", "yiddish"),
+];
+
+/// Reduces a language tag to its lowercased primary subtag, `pt-BR` is `pt`. `None` unless that
+/// is a two or three letter code.
+pub fn normalize_language(language: &str) -> Option<String> {
+    let language = language
+        .trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    match (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_lowercase()) {
+        true => Some(language),
+        false => None,
+    }
+}
+
+/// Text search configuration of a language, `simple` (no stemming) for languages Postgres has no
+/// configuration for.
+pub fn search_config(language: &str) -> &'static str {
+    SEARCH_CONFIGS
+        .iter()
+        .find(|(code, _)| *code == language)
+        .map_or("simple", |(_, config)| config)
+}
+
+/// Every text search configuration articles are indexed with, `simple` included.
+pub fn search_configs() -> impl Iterator<Item = &'static str> {
+    SEARCH_CONFIGS
+        .iter()
+        .map(|(_, config)| *config)
+        .chain(["simple"])
+}
@@ -6,18 +6,62 @@ pub mod auth;
 pub mod auth_interceptor;
 #[path = "auth/consts.rs"]
 pub mod consts;
+#[path = "content_filter.rs"]
+pub mod content_filter;
+#[path = "content_render.rs"]
+pub mod content_render;
+#[path = "errors.rs"]
+pub mod errors;
+#[path = "feed_render.rs"]
+pub mod feed_render;
+#[path = "endpoints/feeds.rs"]
+pub mod feeds;
+#[path = "jobs/filter_reloader.rs"]
+pub mod filter_reloader;
 #[path = "infrastructure.rs"]
 pub mod infrastructure;
+#[path = "languages.rs"]
+pub mod languages;
+#[path = "media/local_storage.rs"]
+pub mod local_storage;
 #[path = "mappers.rs"]
 pub mod mappers;
+#[path = "jobs/media_collector.rs"]
+pub mod media_collector;
+#[path = "media/media_processing.rs"]
+pub mod media_processing;
+#[path = "media/media_storage.rs"]
+pub mod media_storage;
 #[path = "endpoints/news.rs"]
 pub mod news;
+#[path = "pagination.rs"]
+pub mod pagination;
+#[path = "jobs/publish_scheduler.rs"]
+pub mod publish_scheduler;
 #[path = "auth/reflection_middleware.rs"]
 pub mod reflection_middleware;
+#[path = "related_cache.rs"]
+pub mod related_cache;
+#[path = "revision_diff.rs"]
+pub mod revision_diff;
+#[path = "media/s3_storage.rs"]
+pub mod s3_storage;
+#[path = "jobs/score_refresher.rs"]
+pub mod score_refresher;
 #[path = "settings.rs"]
 pub mod settings;
+#[path = "slug.rs"]
+pub mod slug;
+#[path = "tags.rs"]
+pub mod tags;
+#[path = "jobs/trash_purger.rs"]
+pub mod trash_purger;
 #[path = "utils.rs"]
 mod utils;
+#[path = "jobs/view_flusher.rs"]
+pub mod view_flusher;
+#[path = "view_recorder.rs"]
+pub mod view_recorder;
 #[path = "../../target/generated/news.rs"]
 pub mod news_generated {
     include!(concat!(env!("PROTO_OUT_DIR"), "/news.rs"));
@@ -4,10 +4,18 @@ use dotenvy::dotenv;
 use news_api::app_state::AppState;
 use news_api::auth_generated::auth_service_server::AuthServiceServer;
 use news_api::auth_interceptor::AuthInterceptor;
+use news_api::feeds::serve_feeds;
+use news_api::filter_reloader::run_filter_reloader;
+use news_api::media_collector::run_media_collector;
 use news_api::news_generated::news_service_server::NewsServiceServer;
+use news_api::publish_scheduler::run_publish_scheduler;
 use news_api::reflection_middleware::ReflectionMiddlewareLayer;
+use news_api::score_refresher::run_score_refresher;
 use news_api::settings::Settings;
+use news_api::trash_purger::run_trash_purger;
+use news_api::view_flusher::run_view_flusher;
 use std::sync::Arc;
+use tokio::net::TcpListener;
 use tonic::codegen::InterceptedService;
 use tonic::transport::Server;
 
@@ -23,6 +31,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let settings: Settings = config
         .try_deserialize()
         .context("[news-api] [config] Failed to deserialize config")?;
+    settings.validate()?;
 
     let app_state = AppState::new(Arc::new(settings.clone()))?;
     let auth_interceptor = AuthInterceptor::new(app_state.clone());
@@ -30,6 +39,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let sock_addr = settings.app.get_sock_address()?;
 
+    tokio::spawn(run_publish_scheduler(app_state.clone()));
+    tokio::spawn(run_trash_purger(app_state.clone()));
+    tokio::spawn(run_media_collector(app_state.clone()));
+    tokio::spawn(run_score_refresher(app_state.clone()));
+    tokio::spawn(run_view_flusher(app_state.clone()));
+    tokio::spawn(run_filter_reloader(app_state.clone()));
+
+    if let Some(feeds) = &settings.app.feeds {
+        let feeds_listener = TcpListener::bind(feeds.get_sock_address(&settings.app.host)?)
+            .await
+            .context("[news-api] [feeds] failed to bind listener")?;
+        tokio::spawn(serve_feeds(app_state.clone(), feeds_listener));
+    }
+
     Server::builder()
         .layer(reflection_layer)
         .add_service(InterceptedService::new(
@@ -1,17 +1,407 @@
-use crate::news_generated::Article;
-use db_schema::models::ArticleEntry;
+use crate::news_generated::{
+    Article, ArticleRevision, ArticleStatsBucket, ArticleStatus, Bookmark, ContentFormat,
+    Contributor, ContributorInvitation, ContributorRole, Media, ModerationAction, ReadingList,
+    Report, ReportReason, ReportStatus, Section, Tag,
+};
+use db_schema::models::{
+    ArticleBodyEntry, ArticleEntry, ArticleRevisionEntry, ArticleStatsBucketEntry,
+    ArticleSummaryEntry, BookmarkEntry, Byline, ContributorEntry, ContributorInvitationEntry,
+    MediaEntry, ReadingListEntry, ReportEntry, SectionEntry, TagEntry,
+};
+use diesel::internal::derives::multiconnection::chrono::NaiveDateTime;
 
-pub fn into_article(article_entry: ArticleEntry) -> Article {
+/// Legacy ids are left zero unless `accept_legacy_ids`.
+pub fn into_article(article_entry: ArticleEntry, accept_legacy_ids: bool) -> Article {
     Article {
-        id: article_entry.id,
+        id: article_entry.public_id.to_string(),
+        legacy_id: into_legacy_id(article_entry.id, accept_legacy_ids),
+        author_id: article_entry.author_public_id.to_string(),
         author_username: article_entry.author_username,
         title: article_entry.title,
         content: article_entry.content,
         created_at: article_entry.created_at.to_string(),
         tags: article_entry.tags,
+        status: into_article_status(&article_entry.status).into(),
+        publish_at: into_optional_timestamp(article_entry.publish_at),
+        version: article_entry.version,
+        deleted_at: into_optional_timestamp(article_entry.deleted_at),
+        slug: article_entry.slug,
+        content_format: into_content_format(&article_entry.content_format).into(),
+        content_html: article_entry.content_html,
+        excerpt: article_entry.excerpt,
+        reading_time_minutes: article_entry.reading_time_minutes,
+        cover_media_id: article_entry
+            .cover_media_id
+            .map(|media_id| media_id.to_string())
+            .unwrap_or_default(),
+        media_ids: article_entry
+            .media_ids
+            .iter()
+            .map(|media_id| media_id.to_string())
+            .collect(),
+        view_count: article_entry.view_count,
+        bookmarked_by_me: article_entry.bookmarked_by_me,
+        section_id: article_entry.section_public_id.to_string(),
+        section_path: article_entry.section_path,
+        byline: into_byline(article_entry.byline),
+        language: article_entry.language,
+        translation_group_id: article_entry.translation_group_id.to_string(),
+        hidden: article_entry.hidden,
     }
 }
 
-pub fn into_articles(article_entry: Vec<ArticleEntry>) -> Vec<Article> {
-    article_entry.into_iter().map(into_article).collect()
+/// Maps a list view entry, the content fields stay empty until [`fill_article_body`].
+pub fn into_article_summary(
+    summary_entry: ArticleSummaryEntry,
+    accept_legacy_ids: bool,
+) -> Article {
+    Article {
+        id: summary_entry.public_id.to_string(),
+        legacy_id: into_legacy_id(summary_entry.id, accept_legacy_ids),
+        author_id: summary_entry.author_public_id.to_string(),
+        author_username: summary_entry.author_username,
+        title: summary_entry.title,
+        created_at: summary_entry.created_at.to_string(),
+        tags: summary_entry.tags,
+        status: into_article_status(&summary_entry.status).into(),
+        publish_at: into_optional_timestamp(summary_entry.publish_at),
+        version: summary_entry.version,
+        deleted_at: into_optional_timestamp(summary_entry.deleted_at),
+        slug: summary_entry.slug,
+        content_format: into_content_format(&summary_entry.content_format).into(),
+        excerpt: summary_entry.excerpt,
+        reading_time_minutes: summary_entry.reading_time_minutes,
+        cover_media_id: summary_entry
+            .cover_media_id
+            .map(|media_id| media_id.to_string())
+            .unwrap_or_default(),
+        view_count: summary_entry.view_count,
+        bookmarked_by_me: summary_entry.bookmarked_by_me,
+        section_id: summary_entry.section_public_id.to_string(),
+        section_path: summary_entry.section_path,
+        byline: into_byline(summary_entry.byline),
+        language: summary_entry.language,
+        translation_group_id: summary_entry.translation_group_id.to_string(),
+        hidden: summary_entry.hidden,
+        ..Default::default()
+    }
+}
+
+pub fn fill_article_body(article: &mut Article, body_entry: ArticleBodyEntry) {
+    article.content = body_entry.content;
+    article.content_html = body_entry.content_html;
+    article.media_ids = body_entry
+        .media_ids
+        .iter()
+        .map(|media_id| media_id.to_string())
+        .collect();
+}
+
+pub fn into_article_revision(
+    revision_entry: ArticleRevisionEntry,
+    accept_legacy_ids: bool,
+) -> ArticleRevision {
+    ArticleRevision {
+        article_id: revision_entry.article_public_id.to_string(),
+        legacy_article_id: into_legacy_id(revision_entry.article_id, accept_legacy_ids),
+        revision: revision_entry.revision,
+        editor_username: revision_entry.editor_username,
+        title: revision_entry.title,
+        content: revision_entry.content,
+        tags: revision_entry.tags,
+        created_at: revision_entry.created_at.to_string(),
+        content_format: into_content_format(&revision_entry.content_format).into(),
+    }
+}
+
+pub fn into_article_revisions(
+    revision_entries: Vec<ArticleRevisionEntry>,
+    accept_legacy_ids: bool,
+) -> Vec<ArticleRevision> {
+    revision_entries
+        .into_iter()
+        .map(|revision_entry| into_article_revision(revision_entry, accept_legacy_ids))
+        .collect()
+}
+
+pub fn into_media(media_entry: MediaEntry) -> Media {
+    Media {
+        id: media_entry.public_id.to_string(),
+        file_name: media_entry.file_name,
+        content_type: media_entry.content_type,
+        size_bytes: media_entry.size_bytes,
+        width: media_entry.width,
+        height: media_entry.height,
+        created_at: media_entry.created_at.to_string(),
+    }
+}
+
+pub fn into_article_stats_bucket(bucket_entry: ArticleStatsBucketEntry) -> ArticleStatsBucket {
+    ArticleStatsBucket {
+        start_time: bucket_entry.bucket_start.to_string(),
+        view_count: bucket_entry.view_count,
+        like_count: bucket_entry.like_count,
+        comment_count: bucket_entry.comment_count,
+    }
+}
+
+/// Maps a bookmark, the article is only given by bookmark lists.
+pub fn into_bookmark(bookmark_entry: BookmarkEntry, article: Option<Article>) -> Bookmark {
+    Bookmark {
+        article_id: bookmark_entry.article_public_id.to_string(),
+        reading_list_id: bookmark_entry
+            .reading_list_public_id
+            .map(|reading_list_id| reading_list_id.to_string())
+            .unwrap_or_default(),
+        created_at: bookmark_entry.bookmarked_at.to_string(),
+        article,
+    }
+}
+
+/// Maps the byline of an article, every entry is an accepted contributor.
+fn into_byline(byline: Byline) -> Vec<Contributor> {
+    byline
+        .0
+        .into_iter()
+        .map(|entry| Contributor {
+            user_id: entry.user_id.to_string(),
+            username: entry.username,
+            role: into_contributor_role(&entry.role).into(),
+            pending: false,
+        })
+        .collect()
+}
+
+pub fn into_contributor(contributor_entry: ContributorEntry) -> Contributor {
+    Contributor {
+        user_id: contributor_entry.user_public_id.to_string(),
+        username: contributor_entry.username,
+        role: into_contributor_role(&contributor_entry.role).into(),
+        pending: contributor_entry.accepted_at.is_none(),
+    }
+}
+
+pub fn into_contributors(contributor_entries: Vec<ContributorEntry>) -> Vec<Contributor> {
+    contributor_entries
+        .into_iter()
+        .map(into_contributor)
+        .collect()
+}
+
+pub fn into_contributor_invitation(
+    invitation_entry: ContributorInvitationEntry,
+) -> ContributorInvitation {
+    ContributorInvitation {
+        article_id: invitation_entry.article_public_id.to_string(),
+        article_title: invitation_entry.article_title,
+        role: into_contributor_role(&invitation_entry.role).into(),
+        invited_by_username: invitation_entry.invited_by_username.unwrap_or_default(),
+        created_at: invitation_entry.created_at.to_string(),
+    }
+}
+
+pub fn into_reading_list(reading_list_entry: ReadingListEntry) -> ReadingList {
+    ReadingList {
+        id: reading_list_entry.public_id.to_string(),
+        name: reading_list_entry.name,
+        bookmark_count: reading_list_entry.bookmark_count,
+        created_at: reading_list_entry.created_at.to_string(),
+    }
+}
+
+pub fn into_reading_lists(reading_list_entries: Vec<ReadingListEntry>) -> Vec<ReadingList> {
+    reading_list_entries
+        .into_iter()
+        .map(into_reading_list)
+        .collect()
+}
+
+pub fn into_report(report_entry: ReportEntry) -> Report {
+    Report {
+        id: report_entry.public_id.to_string(),
+        article_id: report_entry.article_public_id.to_string(),
+        article_title: report_entry.article_title,
+        comment_id: report_entry
+            .comment_public_id
+            .map(|comment_id| comment_id.to_string())
+            .unwrap_or_default(),
+        comment_content: report_entry.comment_content.unwrap_or_default(),
+        reason: into_report_reason(&report_entry.reason).into(),
+        details: report_entry.details,
+        held_by: report_entry.held_by.unwrap_or_default(),
+        status: into_report_status(&report_entry.status).into(),
+        reporter_username: report_entry.reporter_username.unwrap_or_default(),
+        created_at: report_entry.created_at.to_string(),
+        action: report_entry
+            .action
+            .as_deref()
+            .map_or(
+                ModerationAction::Unspecified,
+                into_moderation_action,
+            )
+            .into(),
+        moderator_username: report_entry.moderator_username.unwrap_or_default(),
+        moderation_reason: report_entry.moderation_reason.unwrap_or_default(),
+        moderated_at: into_optional_timestamp(report_entry.moderated_at),
+    }
+}
+
+pub fn into_reports(report_entries: Vec<ReportEntry>) -> Vec<Report> {
+    report_entries.into_iter().map(into_report).collect()
+}
+
+pub fn into_section(section_entry: SectionEntry) -> Section {
+    Section {
+        id: section_entry.public_id.to_string(),
+        parent_id: section_entry
+            .parent_public_id
+            .map(|parent_id| parent_id.to_string())
+            .unwrap_or_default(),
+        name: section_entry.name,
+        slug: section_entry.slug,
+        path: section_entry.path,
+        article_count: section_entry.article_count,
+    }
+}
+
+pub fn into_sections(section_entries: Vec<SectionEntry>) -> Vec<Section> {
+    section_entries.into_iter().map(into_section).collect()
+}
+
+pub fn into_tag(tag_entry: TagEntry) -> Tag {
+    Tag {
+        name: tag_entry.name,
+        article_count: tag_entry.article_count,
+    }
+}
+
+pub fn into_tags(tag_entries: Vec<TagEntry>) -> Vec<Tag> {
+    tag_entries.into_iter().map(into_tag).collect()
+}
+
+pub fn into_article_status(status: &str) -> ArticleStatus {
+    match status {
+        "draft" => ArticleStatus::Draft,
+        "scheduled" => ArticleStatus::Scheduled,
+        "published" => ArticleStatus::Published,
+        "archived" => ArticleStatus::Archived,
+        _ => ArticleStatus::Unspecified,
+    }
+}
+
+pub fn from_article_status(status: ArticleStatus) -> &'static str {
+    match status {
+        ArticleStatus::Unspecified | ArticleStatus::Draft => "draft",
+        ArticleStatus::Scheduled => "scheduled",
+        ArticleStatus::Published => "published",
+        ArticleStatus::Archived => "archived",
+    }
+}
+
+pub fn into_contributor_role(role: &str) -> ContributorRole {
+    match role {
+        "author" => ContributorRole::Author,
+        "co_author" => ContributorRole::CoAuthor,
+        "editor" => ContributorRole::Editor,
+        "photographer" => ContributorRole::Photographer,
+        _ => ContributorRole::Unspecified,
+    }
+}
+
+pub fn from_contributor_role(role: ContributorRole) -> &'static str {
+    match role {
+        ContributorRole::Author => "author",
+        ContributorRole::Unspecified | ContributorRole::CoAuthor => "co_author",
+        ContributorRole::Editor => "editor",
+        ContributorRole::Photographer => "photographer",
+    }
+}
+
+pub fn into_report_reason(reason: &str) -> ReportReason {
+    match reason {
+        "spam" => ReportReason::Spam,
+        "abuse" => ReportReason::Abuse,
+        "misinformation" => ReportReason::Misinformation,
+        "other" => ReportReason::Other,
+        _ => ReportReason::Unspecified,
+    }
+}
+
+pub fn from_report_reason(reason: ReportReason) -> &'static str {
+    match reason {
+        ReportReason::Spam => "spam",
+        ReportReason::Abuse => "abuse",
+        ReportReason::Misinformation => "misinformation",
+        ReportReason::Unspecified | ReportReason::Other => "other",
+    }
+}
+
+pub fn into_report_status(status: &str) -> ReportStatus {
+    match status {
+        "open" => ReportStatus::Open,
+        "resolved" => ReportStatus::Resolved,
+        "dismissed" => ReportStatus::Dismissed,
+        _ => ReportStatus::Unspecified,
+    }
+}
+
+pub fn from_report_status(status: ReportStatus) -> &'static str {
+    match status {
+        ReportStatus::Unspecified | ReportStatus::Open => "open",
+        ReportStatus::Resolved => "resolved",
+        ReportStatus::Dismissed => "dismissed",
+    }
+}
+
+pub fn into_moderation_action(action: &str) -> ModerationAction {
+    match action {
+        "dismiss" => ModerationAction::Dismiss,
+        "hide" => ModerationAction::Hide,
+        "delete" => ModerationAction::Delete,
+        "warn" => ModerationAction::Warn,
+        "approve" => ModerationAction::Approve,
+        _ => ModerationAction::Unspecified,
+    }
+}
+
+/// Storage name of the action, none for the unspecified one.
+pub fn from_moderation_action(action: ModerationAction) -> Option<&'static str> {
+    match action {
+        ModerationAction::Unspecified => None,
+        ModerationAction::Dismiss => Some("dismiss"),
+        ModerationAction::Hide => Some("hide"),
+        ModerationAction::Delete => Some("delete"),
+        ModerationAction::Warn => Some("warn"),
+        ModerationAction::Approve => Some("approve"),
+    }
+}
+
+pub fn into_content_format(content_format: &str) -> ContentFormat {
+    match content_format {
+        "plain" => ContentFormat::Plain,
+        "markdown" => ContentFormat::Markdown,
+        "html" => ContentFormat::Html,
+        _ => ContentFormat::Unspecified,
+    }
+}
+
+pub fn from_content_format(content_format: ContentFormat) -> &'static str {
+    match content_format {
+        ContentFormat::Unspecified | ContentFormat::Plain => "plain",
+        ContentFormat::Markdown => "markdown",
+        ContentFormat::Html => "html",
+    }
+}
+
+pub fn into_optional_timestamp(timestamp: Option<NaiveDateTime>) -> String {
+    timestamp.map(|t| t.to_string()).unwrap_or_default()
+}
+
+/// Numeric id for clients that have not moved to public ids yet, zero once it no longer fits or
+/// legacy ids are no longer accepted, so the sequence is not exposed.
+pub fn into_legacy_id(id: i64, accept_legacy_ids: bool) -> i32 {
+    match accept_legacy_ids {
+        true => i32::try_from(id).unwrap_or_default(),
+        false => 0,
+    }
 }
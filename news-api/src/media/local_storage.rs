@@ -0,0 +1,46 @@
+use crate::media_storage::MediaStorage;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use tokio::fs;
+
+/// Stores media as files under a root directory, keys are relative paths.
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: &str) -> Self {
+        Self {
+            root: PathBuf::from(root),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl MediaStorage for LocalStorage {
+    async fn put(&self, key: &str, _content_type: &str, data: &[u8]) -> Result<()> {
+        let path = self.root.join(key);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .context("[news-api] failed to create media directory")?;
+        }
+
+        fs::write(&path, data)
+            .await
+            .context("[news-api] failed to write media file")
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        fs::read(self.root.join(key))
+            .await
+            .context("[news-api] failed to read media file")
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        fs::remove_file(self.root.join(key))
+            .await
+            .context("[news-api] failed to delete media file")
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::{anyhow, Context, Result};
+use image::ImageFormat;
+use std::io::Cursor;
+use uuid::Uuid;
+
+/// URL scheme of inline media references in article content, e.g. `![photo](media:<id>)`.
+pub const MEDIA_URL_SCHEME: &str = "media";
+
+const ALLOWED_CONTENT_TYPES: [&str; 4] = ["image/jpeg", "image/png", "image/gif", "image/webp"];
+const MEDIA_ID_LENGTH: usize = 36;
+
+pub struct ProcessedImage {
+    pub width: u32,
+    pub height: u32,
+    pub thumbnail: Vec<u8>,
+}
+
+/// Detects the content type from the leading bytes, only allowed image types are recognized.
+pub fn sniff_content_type(data: &[u8]) -> Option<&'static str> {
+    infer::get(data)
+        .map(|kind| kind.mime_type())
+        .filter(|mime_type| ALLOWED_CONTENT_TYPES.contains(mime_type))
+}
+
+/// Decodes the image and scales it down to fit a `size`x`size` box, keeping the source format.
+pub fn process_image(data: &[u8], content_type: &str, size: u32) -> Result<ProcessedImage> {
+    let format = ImageFormat::from_mime_type(content_type)
+        .ok_or_else(|| anyhow!("[news-api] unsupported image type: {content_type}"))?;
+
+    let image = image::load_from_memory_with_format(data, format)
+        .context("[news-api] failed to decode image")?;
+
+    let mut thumbnail = Cursor::new(Vec::new());
+    image
+        .thumbnail(size, size)
+        .write_to(&mut thumbnail, format)
+        .context("[news-api] failed to encode thumbnail")?;
+
+    Ok(ProcessedImage {
+        width: image.width(),
+        height: image.height(),
+        thumbnail: thumbnail.into_inner(),
+    })
+}
+
+/// Collects the distinct media ids referenced as `media:<id>` in the content, in order of
+/// appearance.
+pub fn extract_media_refs(content: &str) -> Vec<Uuid> {
+    let prefix = format!("{MEDIA_URL_SCHEME}:");
+    let mut media_ids = Vec::new();
+
+    for (position, _) in content.match_indices(&prefix) {
+        let start = position + prefix.len();
+        let media_id = content
+            .get(start..start + MEDIA_ID_LENGTH)
+            .and_then(|id| Uuid::parse_str(id).ok());
+
+        if let Some(media_id) = media_id {
+            if !media_ids.contains(&media_id) {
+                media_ids.push(media_id);
+            }
+        }
+    }
+
+    media_ids
+}
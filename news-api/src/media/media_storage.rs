@@ -0,0 +1,30 @@
+use crate::local_storage::LocalStorage;
+use crate::s3_storage::S3Storage;
+use crate::settings::MediaSettings;
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+
+/// Object storage for uploaded media, addressed by opaque keys.
+#[tonic::async_trait]
+pub trait MediaStorage: Send + Sync {
+    async fn put(&self, key: &str, content_type: &str, data: &[u8]) -> Result<()>;
+    async fn get(&self, key: &str) -> Result<Vec<u8>>;
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+pub fn build_media_storage(settings: &MediaSettings) -> Result<Arc<dyn MediaStorage>> {
+    match settings.backend.as_str() {
+        "local" => Ok(Arc::new(LocalStorage::new(&settings.local_root))),
+        "s3" => {
+            let s3_settings = settings
+                .s3
+                .as_ref()
+                .ok_or_else(|| anyhow!("[news-api] s3 media backend requires s3 settings"))?;
+
+            Ok(Arc::new(S3Storage::new(s3_settings)?))
+        }
+        backend => Err(anyhow!(
+            "[news-api] unknown media backend: {backend}"
+        )),
+    }
+}
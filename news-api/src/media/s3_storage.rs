@@ -0,0 +1,63 @@
+use crate::media_storage::MediaStorage;
+use crate::settings::S3Settings;
+use anyhow::{Context, Result};
+use s3::creds::Credentials;
+use s3::{Bucket, Region};
+
+/// Stores media in an S3-compatible bucket (AWS, MinIO), addressed with path-style URLs.
+pub struct S3Storage {
+    bucket: Box<Bucket>,
+}
+
+impl S3Storage {
+    pub fn new(settings: &S3Settings) -> Result<Self> {
+        let region = Region::Custom {
+            region: settings.region.clone(),
+            endpoint: settings.endpoint.clone(),
+        };
+        let credentials = Credentials {
+            access_key: Some(settings.access_key.clone()),
+            secret_key: Some(settings.secret_key.clone()),
+            security_token: None,
+            session_token: None,
+            expiration: None,
+        };
+
+        let bucket = Bucket::new(&settings.bucket, region, credentials)
+            .context("[news-api] failed to configure s3 bucket")?
+            .with_path_style();
+
+        Ok(Self { bucket })
+    }
+}
+
+#[tonic::async_trait]
+impl MediaStorage for S3Storage {
+    async fn put(&self, key: &str, content_type: &str, data: &[u8]) -> Result<()> {
+        self.bucket
+            .put_object_with_content_type(key, data, content_type)
+            .await
+            .context("[news-api] failed to put media object")?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let response = self
+            .bucket
+            .get_object(key)
+            .await
+            .context("[news-api] failed to get media object")?;
+
+        Ok(response.to_vec())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.bucket
+            .delete_object(key)
+            .await
+            .context("[news-api] failed to delete media object")?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,513 @@
+// Page tokens are rejected with tonic's `Status` like the handlers do, see `news`.
+#![allow(clippy::result_large_err)]
+
+use diesel::internal::derives::multiconnection::chrono::{DateTime, NaiveDateTime};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use tonic::Status;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const SIGNATURE_LENGTH: usize = 16;
+
+/// Position of a row in a keyset-ordered list, the sort key values followed by a unique
+/// tie-breaker, serialized into page tokens.
+pub trait CursorPosition: Clone + Sized {
+    fn encode(&self) -> String;
+    fn decode(value: &str) -> Option<Self>;
+}
+
+/// Timestamp (in microseconds) and id, e.g. articles by publication time.
+impl CursorPosition for (NaiveDateTime, i64) {
+    fn encode(&self) -> String {
+        format!(
+            "{}:{}",
+            self.0.and_utc().timestamp_micros(),
+            self.1
+        )
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        let (micros, id) = value.split_once(':')?;
+        let timestamp = DateTime::from_timestamp_micros(micros.parse().ok()?)?.naive_utc();
+
+        Some((timestamp, id.parse().ok()?))
+    }
+}
+
+/// Count and name, e.g. tags by usage. The name goes last as it may contain the separator.
+impl CursorPosition for (i64, String) {
+    fn encode(&self) -> String {
+        format!("{}:{}", self.0, self.1)
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        let (count, name) = value.split_once(':')?;
+
+        Some((count.parse().ok()?, name.to_string()))
+    }
+}
+
+/// Sort key of a list that can be ordered in several ways, e.g. articles by time or score.
+#[derive(Clone, Copy)]
+pub enum SortKey {
+    Timestamp(NaiveDateTime),
+    Count(i64),
+    Score(f64),
+}
+
+/// Sort key tagged with its kind, and id.
+impl CursorPosition for (SortKey, i64) {
+    fn encode(&self) -> String {
+        let key = match self.0 {
+            SortKey::Timestamp(timestamp) => format!("t{}", timestamp.and_utc().timestamp_micros()),
+            SortKey::Count(count) => format!("c{count}"),
+            SortKey::Score(score) => format!("s{score}"),
+        };
+
+        format!("{key}:{}", self.1)
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        let (key, id) = value.split_once(':')?;
+        let (kind, key) = key.split_at_checked(1)?;
+        let key = match kind {
+            "t" => {
+                SortKey::Timestamp(DateTime::from_timestamp_micros(key.parse().ok()?)?.naive_utc())
+            }
+            "c" => SortKey::Count(key.parse().ok()?),
+            "s" => SortKey::Score(key.parse().ok().filter(|score: &f64| score.is_finite())?),
+            _ => return None,
+        };
+
+        Some((key, id.parse().ok()?))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PageDirection {
+    /// Rows after the position in list order.
+    Next,
+    /// Rows before the position, loaded in reverse list order.
+    Prev,
+}
+
+/// Decoded page token: where the requested page starts and which way it goes.
+#[derive(Clone)]
+pub struct PageCursor<P> {
+    pub direction: PageDirection,
+    pub position: P,
+}
+
+/// Page of rows in list order with the tokens of its neighbours, empty when there is none.
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_page_token: String,
+    pub prev_page_token: String,
+}
+
+/// Signs page tokens of one list RPC, `scope` and the hash of the list's filter keep tokens of
+/// one list from being replayed against another one or other filters.
+pub struct PageTokens {
+    scope: &'static str,
+    filter_hash: [u8; 32],
+    key: [u8; 32],
+}
+
+impl PageTokens {
+    /// `filter` is any stable rendering of the filters the list was asked with.
+    pub fn new(scope: &'static str, filter: &str, secret_key: &str) -> Self {
+        Self {
+            scope,
+            filter_hash: Sha256::digest(filter.as_bytes()).into(),
+            key: derive_key(secret_key),
+        }
+    }
+
+    pub fn encode<P: CursorPosition>(&self, cursor: &PageCursor<P>) -> String {
+        let direction = match cursor.direction {
+            PageDirection::Next => 'n',
+            PageDirection::Prev => 'p',
+        };
+        let payload = format!("{direction}{}", cursor.position.encode());
+
+        format!(
+            "{}.{}",
+            hex::encode(&payload),
+            self.sign(&payload)
+        )
+    }
+
+    /// Decodes a page token, an empty token means the first page.
+    pub fn decode<P: CursorPosition>(
+        &self,
+        page_token: &str,
+    ) -> Result<Option<PageCursor<P>>, Status> {
+        if page_token.is_empty() {
+            return Ok(None);
+        }
+
+        self.verify(page_token)
+            .and_then(|payload| {
+                let direction = match payload.chars().next()? {
+                    'n' => PageDirection::Next,
+                    'p' => PageDirection::Prev,
+                    _ => return None,
+                };
+
+                Some(PageCursor {
+                    direction,
+                    position: P::decode(&payload[1..])?,
+                })
+            })
+            .map(Some)
+            .ok_or_else(|| Status::invalid_argument("Invalid page_token"))
+    }
+
+    /// Builds the page from rows loaded with [`page_limit`] in cursor direction: drops the probe
+    /// row, restores list order and derives the neighbour tokens from the boundary rows.
+    pub fn page<T, P: CursorPosition>(
+        &self,
+        mut rows: Vec<T>,
+        page_size: i64,
+        cursor: Option<&PageCursor<P>>,
+        position: impl Fn(&T) -> P,
+    ) -> Page<T> {
+        let has_more = rows.len() as i64 > page_size;
+        rows.truncate(page_size as usize);
+
+        let direction = cursor.map_or(PageDirection::Next, |cursor| cursor.direction);
+        if direction == PageDirection::Prev {
+            rows.reverse();
+        }
+
+        // Going forward there is a previous page whenever we didn't start at the top, going
+        // backward there is always the page we came from.
+        let (has_next, has_prev) = match direction {
+            PageDirection::Next => (has_more, cursor.is_some()),
+            PageDirection::Prev => (true, has_more),
+        };
+
+        let token = |direction, row: Option<&T>| {
+            let position = row
+                .map(&position)
+                .or_else(|| cursor.map(|cursor| cursor.position.clone()));
+
+            position
+                .map(|position| {
+                    self.encode(&PageCursor {
+                        direction,
+                        position,
+                    })
+                })
+                .unwrap_or_default()
+        };
+
+        Page {
+            next_page_token: match has_next {
+                true => token(PageDirection::Next, rows.last()),
+                false => String::new(),
+            },
+            prev_page_token: match has_prev {
+                true => token(PageDirection::Prev, rows.first()),
+                false => String::new(),
+            },
+            items: rows,
+        }
+    }
+
+    fn sign(&self, payload: &str) -> String {
+        hex::encode(&self.mac(payload).finalize().into_bytes()[..SIGNATURE_LENGTH])
+    }
+
+    fn verify(&self, page_token: &str) -> Option<String> {
+        let (payload, signature) = page_token.split_once('.')?;
+        let payload = String::from_utf8(hex::decode(payload).ok()?).ok()?;
+        let signature = hex::decode(signature).ok()?;
+
+        if signature.len() != SIGNATURE_LENGTH {
+            return None;
+        }
+
+        self.mac(&payload)
+            .verify_truncated_left(&signature)
+            .ok()
+            .map(|_| payload)
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).unwrap();
+
+        mac.update(self.scope.as_bytes());
+        // Separates the scope from the fixed-size hash, no scope is a prefix of another one then.
+        mac.update(&[0]);
+        mac.update(&self.filter_hash);
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+/// Page tokens get a key of their own, derived from the server secret, so a signature never
+/// doubles as one of the session ids signed with the secret itself.
+fn derive_key(secret_key: &str) -> [u8; 32] {
+    // HMAC takes keys of any length.
+    let mut mac = HmacSha256::new_from_slice(secret_key.as_bytes()).unwrap();
+
+    mac.update(b"news-api page tokens");
+    mac.finalize().into_bytes().into()
+}
+
+/// Clamps the requested page size, zero or less picks the default.
+pub fn resolve_page_size(page_size: i64, default: i64, max: i64) -> i64 {
+    match page_size {
+        size if size <= 0 => default,
+        size => size.min(max),
+    }
+}
+
+/// Rows to load for a page: one more than its size tells whether another page follows.
+pub fn page_limit(page_size: i64) -> i64 {
+    page_size + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens() -> PageTokens {
+        PageTokens::new("tags", "", "secret")
+    }
+
+    fn timestamp(micros: i64) -> NaiveDateTime {
+        DateTime::from_timestamp_micros(micros).unwrap().naive_utc()
+    }
+
+    fn cursor(direction: PageDirection, count: i64) -> PageCursor<(i64, String)> {
+        PageCursor {
+            direction,
+            position: (count, format!("tag{count}")),
+        }
+    }
+
+    fn decoded(tokens: &PageTokens, page_token: &str) -> Option<(PageDirection, (i64, String))> {
+        tokens
+            .decode::<(i64, String)>(page_token)
+            .unwrap()
+            .map(|cursor| (cursor.direction, cursor.position))
+    }
+
+    /// Pages over rows `count`, keyed by `(count, "tag{count}")`.
+    fn page(
+        rows: Vec<i64>,
+        page_size: i64,
+        cursor: Option<&PageCursor<(i64, String)>>,
+    ) -> Page<i64> {
+        tokens().page(rows, page_size, cursor, |count| {
+            (*count, format!("tag{count}"))
+        })
+    }
+
+    #[test]
+    fn timestamp_positions_round_trip() {
+        let position = (timestamp(1_700_000_000_123_456), 42);
+
+        let decoded = <(NaiveDateTime, i64)>::decode(&position.encode()).unwrap();
+
+        assert_eq!(decoded, position);
+    }
+
+    #[test]
+    fn names_containing_the_separator_round_trip() {
+        let position = (7, "c++:templates:2".to_string());
+
+        let decoded = <(i64, String)>::decode(&position.encode()).unwrap();
+
+        assert_eq!(decoded, position);
+    }
+
+    #[test]
+    fn sort_keys_round_trip() {
+        let positions = [
+            (
+                SortKey::Timestamp(timestamp(1_700_000_000_000_001)),
+                1,
+            ),
+            (SortKey::Count(-3), 2),
+            (SortKey::Score(0.125), 3),
+        ];
+
+        for position in positions {
+            let decoded = <(SortKey, i64)>::decode(&position.encode()).unwrap();
+
+            assert_eq!(decoded.1, position.1);
+            match (decoded.0, position.0) {
+                (SortKey::Timestamp(decoded), SortKey::Timestamp(expected)) => {
+                    assert_eq!(decoded, expected)
+                }
+                (SortKey::Count(decoded), SortKey::Count(expected)) => {
+                    assert_eq!(decoded, expected)
+                }
+                (SortKey::Score(decoded), SortKey::Score(expected)) => {
+                    assert_eq!(decoded, expected)
+                }
+                _ => panic!("sort key changed its kind"),
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_sort_keys_are_rejected() {
+        for value in ["", "t1", "x1:1", "sNaN:1", "sinf:1", "c1:x", ":1"] {
+            assert!(
+                <(SortKey, i64)>::decode(value).is_none(),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn tokens_round_trip() {
+        let tokens = tokens();
+
+        for direction in [PageDirection::Next, PageDirection::Prev] {
+            let page_token = tokens.encode(&cursor(direction, 5));
+
+            assert_eq!(
+                decoded(&tokens, &page_token),
+                Some((direction, (5, "tag5".to_string())))
+            );
+        }
+    }
+
+    #[test]
+    fn empty_token_is_the_first_page() {
+        assert!(decoded(&tokens(), "").is_none());
+    }
+
+    #[test]
+    fn tampered_tokens_are_rejected() {
+        let tokens = tokens();
+        let page_token = tokens.encode(&cursor(PageDirection::Next, 5));
+        let (payload, signature) = page_token.split_once('.').unwrap();
+
+        let forged_payload = format!("{}.{signature}", hex::encode("n6:tag6"));
+        let mut flipped = hex::decode(signature).unwrap();
+        flipped[0] ^= 1;
+        let forged_signature = format!("{payload}.{}", hex::encode(flipped));
+        let truncated = format!("{payload}.{}", &signature[..signature.len() - 2]);
+        let unsigned = payload.to_string();
+
+        for page_token in [forged_payload, forged_signature, truncated, unsigned] {
+            assert!(
+                tokens.decode::<(i64, String)>(&page_token).is_err(),
+                "{page_token}"
+            );
+        }
+    }
+
+    #[test]
+    fn tokens_of_other_lists_are_rejected() {
+        let page_token = tokens().encode(&cursor(PageDirection::Next, 5));
+
+        let other_scope = PageTokens::new("articles", "", "secret");
+        let other_filter = PageTokens::new("tags", "author=alice", "secret");
+        let other_secret = PageTokens::new("tags", "", "another secret");
+
+        for tokens in [other_scope, other_filter, other_secret] {
+            assert!(tokens.decode::<(i64, String)>(&page_token).is_err());
+        }
+    }
+
+    #[test]
+    fn undecodable_positions_are_rejected() {
+        let tokens = tokens();
+        let page_token = tokens.encode(&cursor(PageDirection::Next, 5));
+
+        assert!(tokens.decode::<(NaiveDateTime, i64)>(&page_token).is_err());
+    }
+
+    #[test]
+    fn first_page_links_only_forward() {
+        let page = page(vec![1, 2, 3], 2, None);
+
+        assert_eq!(page.items, vec![1, 2]);
+        assert!(page.prev_page_token.is_empty());
+        assert_eq!(
+            decoded(&tokens(), &page.next_page_token),
+            Some((PageDirection::Next, (2, "tag2".to_string())))
+        );
+    }
+
+    #[test]
+    fn single_page_links_nowhere() {
+        let page = page(vec![1, 2], 2, None);
+
+        assert_eq!(page.items, vec![1, 2]);
+        assert!(page.next_page_token.is_empty());
+        assert!(page.prev_page_token.is_empty());
+    }
+
+    #[test]
+    fn last_page_going_forward_links_only_back() {
+        let page = page(
+            vec![3, 4],
+            2,
+            Some(&cursor(PageDirection::Next, 2)),
+        );
+
+        assert_eq!(page.items, vec![3, 4]);
+        assert!(page.next_page_token.is_empty());
+        assert_eq!(
+            decoded(&tokens(), &page.prev_page_token),
+            Some((PageDirection::Prev, (3, "tag3".to_string())))
+        );
+    }
+
+    #[test]
+    fn middle_page_going_backward_links_both_ways() {
+        // Rows before position 5 come in reverse list order, the probe row included.
+        let page = page(
+            vec![4, 3, 2],
+            2,
+            Some(&cursor(PageDirection::Prev, 5)),
+        );
+
+        assert_eq!(page.items, vec![3, 4]);
+        assert_eq!(
+            decoded(&tokens(), &page.next_page_token),
+            Some((PageDirection::Next, (4, "tag4".to_string())))
+        );
+        assert_eq!(
+            decoded(&tokens(), &page.prev_page_token),
+            Some((PageDirection::Prev, (3, "tag3".to_string())))
+        );
+    }
+
+    #[test]
+    fn first_page_going_backward_links_only_forward() {
+        let page = page(
+            vec![2, 1],
+            2,
+            Some(&cursor(PageDirection::Prev, 3)),
+        );
+
+        assert_eq!(page.items, vec![1, 2]);
+        assert!(page.prev_page_token.is_empty());
+        assert_eq!(
+            decoded(&tokens(), &page.next_page_token),
+            Some((PageDirection::Next, (2, "tag2".to_string())))
+        );
+    }
+
+    #[test]
+    fn empty_page_links_back_from_its_cursor() {
+        let page = page(vec![], 2, Some(&cursor(PageDirection::Next, 9)));
+
+        assert!(page.items.is_empty());
+        assert!(page.next_page_token.is_empty());
+        assert_eq!(
+            decoded(&tokens(), &page.prev_page_token),
+            Some((PageDirection::Prev, (9, "tag9".to_string())))
+        );
+    }
+}
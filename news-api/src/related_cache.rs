@@ -0,0 +1,54 @@
+use diesel::internal::derives::multiconnection::chrono::{NaiveDateTime, TimeDelta};
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+/// Ranked related articles per article, ranking is too costly to redo on every read. Rankings are
+/// dropped when tags change and expire after the TTL, so newly published articles show up.
+pub struct RelatedCache {
+    ttl: TimeDelta,
+    entries: Mutex<HashMap<i64, CachedRelated>>,
+}
+
+struct CachedRelated {
+    article_ids: Vec<i64>,
+    cached_at: NaiveDateTime,
+}
+
+impl RelatedCache {
+    pub fn new(ttl: TimeDelta) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::default(),
+        }
+    }
+
+    pub fn get(&self, article_id: i64, now: NaiveDateTime) -> Option<Vec<i64>> {
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+
+        entries
+            .get(&article_id)
+            .filter(|entry| now - entry.cached_at < self.ttl)
+            .map(|entry| entry.article_ids.clone())
+    }
+
+    /// Caches the ranking of the article, forgetting the expired ones.
+    pub fn insert(&self, article_id: i64, article_ids: Vec<i64>, now: NaiveDateTime) {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+
+        entries.retain(|_, entry| now - entry.cached_at < self.ttl);
+        entries.insert(
+            article_id,
+            CachedRelated {
+                article_ids,
+                cached_at: now,
+            },
+        );
+    }
+
+    /// Drops every ranking, after tags changed, since any article may now rank differently.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+
+        entries.clear();
+    }
+}
@@ -0,0 +1,49 @@
+use crate::news_generated::{DiffChunk, DiffGranularity, DiffOperation};
+use similar::{ChangeTag, TextDiff};
+
+/// Diffs two texts, merging consecutive changes of the same kind into a single chunk.
+pub fn diff_text(old: &str, new: &str, granularity: DiffGranularity) -> Vec<DiffChunk> {
+    let diff = match granularity {
+        DiffGranularity::Word => TextDiff::from_words(old, new),
+        DiffGranularity::Unspecified | DiffGranularity::Line => TextDiff::from_lines(old, new),
+    };
+
+    let mut chunks: Vec<DiffChunk> = Vec::new();
+    for change in diff.iter_all_changes() {
+        let operation: i32 = into_diff_operation(change.tag()).into();
+
+        match chunks.last_mut() {
+            Some(chunk) if chunk.operation == operation => chunk.value.push_str(change.value()),
+            _ => chunks.push(DiffChunk {
+                operation,
+                value: change.value().to_string(),
+            }),
+        }
+    }
+
+    chunks
+}
+
+/// Returns `(added, removed)` tags between two revisions.
+pub fn diff_tags(old: &[String], new: &[String]) -> (Vec<String>, Vec<String>) {
+    let added = new
+        .iter()
+        .filter(|tag| !old.contains(tag))
+        .cloned()
+        .collect();
+    let removed = old
+        .iter()
+        .filter(|tag| !new.contains(tag))
+        .cloned()
+        .collect();
+
+    (added, removed)
+}
+
+fn into_diff_operation(tag: ChangeTag) -> DiffOperation {
+    match tag {
+        ChangeTag::Equal => DiffOperation::Equal,
+        ChangeTag::Insert => DiffOperation::Insert,
+        ChangeTag::Delete => DiffOperation::Delete,
+    }
+}
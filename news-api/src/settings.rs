@@ -1,13 +1,18 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use diesel::internal::derives::multiconnection::chrono::TimeDelta;
 use serde::Deserialize;
 use std::collections::HashSet;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Settings {
     pub database: DbSettings,
     pub app: AppSettings,
     pub auth: AuthSettings,
+    pub jobs: JobsSettings,
+    pub media: MediaSettings,
+    pub content_filter: ContentFilterSettings,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -22,10 +27,118 @@ pub struct DbSettings {
     pub uri: String,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct JobsSettings {
+    pub publish_interval_secs: u64,
+    pub purge_interval_secs: u64,
+    pub trash_retention_days: i64,
+    pub media_gc_interval_secs: u64,
+    pub media_orphan_retention_hours: i64,
+    pub score_interval_secs: u64,
+    pub view_flush_interval_secs: u64,
+    pub view_dedup_window_mins: i64,
+    /// Viewers remembered for deduplication at most, views of further ones count every time.
+    pub view_dedup_max_viewers: usize,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MediaSettings {
+    /// `local` or `s3`.
+    pub backend: String,
+    pub local_root: String,
+    pub max_upload_bytes: usize,
+    pub thumbnail_size: u32,
+    pub s3: Option<S3Settings>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct S3Settings {
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ContentFilterSettings {
+    /// TOML file with the rules, no rules apply while it is missing.
+    pub rules_path: String,
+    pub reload_interval_secs: u64,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppSettings {
     pub host: String,
     pub port: i32,
+    /// Accept the deprecated numeric article ids next to the public ones.
+    pub accept_legacy_ids: bool,
+    /// RSS and Atom feeds over HTTP, not served when unset.
+    pub feeds: Option<FeedSettings>,
+    /// Comma-separated addresses of the proxies whose `x-forwarded-for` is believed.
+    #[serde(default)]
+    pub trusted_proxies: String,
+    /// Minutes a ranking of related articles is reused before it is computed again.
+    pub related_cache_ttl_mins: i64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FeedSettings {
+    pub port: i32,
+    pub title: String,
+    /// Public address of the site, articles are linked as `{site_url}/articles/{slug}`.
+    pub site_url: String,
+    /// Newest articles listed per feed.
+    pub max_items: i64,
+}
+
+impl Settings {
+    /// Rejects values the server can't run with, `tokio::time::interval` panics on a zero period.
+    pub fn validate(&self) -> Result<()> {
+        let intervals = [
+            (
+                "jobs.publish_interval_secs",
+                self.jobs.publish_interval_secs,
+            ),
+            (
+                "jobs.purge_interval_secs",
+                self.jobs.purge_interval_secs,
+            ),
+            (
+                "jobs.media_gc_interval_secs",
+                self.jobs.media_gc_interval_secs,
+            ),
+            (
+                "jobs.score_interval_secs",
+                self.jobs.score_interval_secs,
+            ),
+            (
+                "jobs.view_flush_interval_secs",
+                self.jobs.view_flush_interval_secs,
+            ),
+            (
+                "content_filter.reload_interval_secs",
+                self.content_filter.reload_interval_secs,
+            ),
+        ];
+
+        if let Some((name, _)) = intervals.iter().find(|(_, secs)| *secs == 0) {
+            bail!("[news-api] [config] {name} must be positive");
+        }
+
+        if let Some(feeds) = &self.app.feeds {
+            if feeds.max_items <= 0 {
+                bail!("[news-api] [config] app.feeds.max_items must be positive");
+            }
+        }
+        if self.app.related_cache_ttl_mins <= 0 {
+            bail!("[news-api] [config] app.related_cache_ttl_mins must be positive");
+        }
+
+        self.app.get_trusted_proxies()?;
+
+        Ok(())
+    }
 }
 
 impl AuthSettings {
@@ -37,10 +150,77 @@ impl AuthSettings {
     }
 }
 
+impl JobsSettings {
+    pub fn get_publish_interval(&self) -> Duration {
+        Duration::from_secs(self.publish_interval_secs)
+    }
+
+    pub fn get_purge_interval(&self) -> Duration {
+        Duration::from_secs(self.purge_interval_secs)
+    }
+
+    pub fn get_trash_retention(&self) -> TimeDelta {
+        TimeDelta::days(self.trash_retention_days)
+    }
+
+    pub fn get_media_gc_interval(&self) -> Duration {
+        Duration::from_secs(self.media_gc_interval_secs)
+    }
+
+    pub fn get_media_orphan_retention(&self) -> TimeDelta {
+        TimeDelta::hours(self.media_orphan_retention_hours)
+    }
+
+    pub fn get_score_interval(&self) -> Duration {
+        Duration::from_secs(self.score_interval_secs)
+    }
+
+    pub fn get_view_flush_interval(&self) -> Duration {
+        Duration::from_secs(self.view_flush_interval_secs)
+    }
+
+    pub fn get_view_dedup_window(&self) -> TimeDelta {
+        TimeDelta::minutes(self.view_dedup_window_mins)
+    }
+}
+
+impl ContentFilterSettings {
+    pub fn get_reload_interval(&self) -> Duration {
+        Duration::from_secs(self.reload_interval_secs)
+    }
+}
+
 impl AppSettings {
     pub fn get_sock_address(&self) -> Result<SocketAddr> {
         format!("{}:{}", self.host, self.port)
             .parse()
             .context("[news-api] failed to parse socket address")
     }
+
+    pub fn get_related_cache_ttl(&self) -> TimeDelta {
+        TimeDelta::minutes(self.related_cache_ttl_mins)
+    }
+
+    pub fn get_trusted_proxies(&self) -> Result<HashSet<IpAddr>> {
+        self.trusted_proxies
+            .split(',')
+            .map(|address| address.trim())
+            .filter(|address| !address.is_empty())
+            .map(|address| {
+                address.parse().with_context(|| {
+                    format!(
+                        "[news-api] [config] app.trusted_proxies has an invalid address: {address}"
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+impl FeedSettings {
+    pub fn get_sock_address(&self, host: &str) -> Result<SocketAddr> {
+        format!("{}:{}", host, self.port)
+            .parse()
+            .context("[news-api] failed to parse feeds socket address")
+    }
 }
@@ -0,0 +1,50 @@
+use deunicode::deunicode;
+
+const MAX_SLUG_LENGTH: usize = 80;
+const FALLBACK_SLUG: &str = "article";
+
+/// Builds a URL-safe slug from a title: transliterates to ASCII, lowercases and joins
+/// alphanumeric runs with `-`.
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::with_capacity(title.len());
+
+    for c in deunicode(title).chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    slug.truncate(MAX_SLUG_LENGTH);
+    let slug = slug.trim_end_matches('-');
+
+    match slug.is_empty() {
+        true => FALLBACK_SLUG.to_string(),
+        false => slug.to_string(),
+    }
+}
+
+/// Checks whether `slug` is `base` itself or `base` with a numeric collision suffix.
+pub fn is_slug_of(slug: &str, base: &str) -> bool {
+    match slug.strip_prefix(base) {
+        Some("") => true,
+        Some(rest) => rest
+            .strip_prefix('-')
+            .is_some_and(|suffix| suffix.parse::<u32>().is_ok()),
+        None => false,
+    }
+}
+
+/// Picks `base`, or the first `base-N` (N >= 2) that is not taken.
+pub fn next_free_slug(base: &str, taken: &[String]) -> String {
+    let mut slug = base.to_string();
+    let mut suffix = 1;
+
+    while taken.contains(&slug) {
+        suffix += 1;
+        slug = format!("{base}-{suffix}");
+    }
+
+    slug
+}
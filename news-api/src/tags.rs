@@ -0,0 +1,41 @@
+const MAX_TAG_LENGTH: usize = 50;
+
+/// Trims, collapses inner whitespace, case-folds and truncates a tag name to the length of
+/// `tags.name`, `None` when nothing is left.
+pub fn normalize_tag(name: &str) -> Option<String> {
+    let name = name
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+        .chars()
+        .take(MAX_TAG_LENGTH)
+        .collect::<String>();
+    let name = name.trim_end();
+
+    match name.is_empty() {
+        true => None,
+        false => Some(name.to_string()),
+    }
+}
+
+/// Normalizes tag names, dropping empty ones and duplicates while keeping the order.
+pub fn normalize_tags(names: Vec<String>) -> Vec<String> {
+    let mut normalized = Vec::with_capacity(names.len());
+
+    for name in names.iter().filter_map(|name| normalize_tag(name)) {
+        if !normalized.contains(&name) {
+            normalized.push(name);
+        }
+    }
+
+    normalized
+}
+
+/// Escapes `LIKE` wildcards so the query matches literally.
+pub fn escape_like(query: &str) -> String {
+    query
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
@@ -1,3 +1,6 @@
+// The request helpers fail with tonic's `Status` like the handlers do, see `news`.
+#![allow(clippy::result_large_err)]
+
 use crate::consts::{SessionId, UserId};
 use anyhow::{anyhow, Context, Result};
 use bcrypt::{hash, verify, DEFAULT_COST};
@@ -14,7 +17,7 @@ pub fn parse_timestamp(timestamp_str: &str) -> Option<NaiveDateTime> {
     NaiveDateTime::parse_from_str(timestamp_str, "%Y-%m-%d %H:%M:%S%.6f").ok()
 }
 
-pub fn generate_session_id(user_id: i32, secret_key: &str) -> Result<String> {
+pub fn generate_session_id(user_id: i64, secret_key: &str) -> Result<String> {
     let mut mac =
         HmacSha256::new_from_slice(secret_key.as_bytes()).context("[news-api] hmac 256 error")?;
 
@@ -68,6 +71,14 @@ pub fn get_user_id<T>(request: &Request<T>) -> Result<UserId, Status> {
     Ok(user_id)
 }
 
+pub fn get_optional_user_id<T>(request: &Request<T>) -> Option<UserId> {
+    request.extensions().get::<UserId>().cloned()
+}
+
+pub fn get_optional_session_id<T>(request: &Request<T>) -> Option<SessionId> {
+    request.extensions().get::<SessionId>().cloned()
+}
+
 pub fn get_session_id<T>(request: &Request<T>) -> Result<SessionId, Status> {
     let session_id = request
         .extensions()
@@ -0,0 +1,97 @@
+use diesel::internal::derives::multiconnection::chrono::{DurationRound, NaiveDateTime, TimeDelta};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+/// Views of an article within one hour, the unit views are flushed and reported in.
+pub struct ViewBucket {
+    pub article_id: i64,
+    pub bucket_start: NaiveDateTime,
+    pub view_count: i64,
+}
+
+/// Buffers article views in memory until the view flusher writes them out in one batch, so reads
+/// don't wait on a write. A viewer is counted once per article within the dedup window, while
+/// no more than `max_viewers` are remembered.
+pub struct ViewRecorder {
+    dedup_window: TimeDelta,
+    max_viewers: usize,
+    buffer: Mutex<ViewBuffer>,
+}
+
+#[derive(Default)]
+struct ViewBuffer {
+    pending: HashMap<(i64, NaiveDateTime), i64>,
+    last_seen: HashMap<(i64, String), NaiveDateTime>,
+}
+
+impl ViewRecorder {
+    pub fn new(dedup_window: TimeDelta, max_viewers: usize) -> Self {
+        Self {
+            dedup_window,
+            max_viewers,
+            buffer: Mutex::default(),
+        }
+    }
+
+    /// Counts a view unless the viewer was already counted for the article within the window,
+    /// views of unknown viewers are always counted, as are those of new viewers while the
+    /// remembered ones are at the limit.
+    pub fn record(&self, article_id: i64, viewer_key: Option<String>, now: NaiveDateTime) {
+        let mut buffer = self.buffer.lock().unwrap_or_else(PoisonError::into_inner);
+        let is_full = buffer.last_seen.len() >= self.max_viewers;
+
+        if let Some(viewer_key) = viewer_key {
+            match buffer.last_seen.entry((article_id, viewer_key)) {
+                Entry::Occupied(seen) if now - *seen.get() < self.dedup_window => return,
+                Entry::Occupied(mut seen) => {
+                    seen.insert(now);
+                }
+                Entry::Vacant(seen) if !is_full => {
+                    seen.insert(now);
+                }
+                Entry::Vacant(_) => {}
+            }
+        }
+
+        let bucket_start = now.duration_trunc(TimeDelta::hours(1)).unwrap_or(now);
+        *buffer
+            .pending
+            .entry((article_id, bucket_start))
+            .or_default() += 1;
+    }
+
+    /// Takes the buffered views and forgets viewers whose dedup window is over, called on every
+    /// flush whether or not there are views to write.
+    pub fn drain(&self, now: NaiveDateTime) -> Vec<ViewBucket> {
+        let mut buffer = self.buffer.lock().unwrap_or_else(PoisonError::into_inner);
+
+        buffer
+            .last_seen
+            .retain(|_, seen| now - *seen < self.dedup_window);
+
+        buffer
+            .pending
+            .drain()
+            .map(
+                |((article_id, bucket_start), view_count)| ViewBucket {
+                    article_id,
+                    bucket_start,
+                    view_count,
+                },
+            )
+            .collect()
+    }
+
+    /// Puts back views that failed to flush, they go out with the next batch.
+    pub fn restore(&self, buckets: Vec<ViewBucket>) {
+        let mut buffer = self.buffer.lock().unwrap_or_else(PoisonError::into_inner);
+
+        for bucket in buckets {
+            *buffer
+                .pending
+                .entry((bucket.article_id, bucket.bucket_start))
+                .or_default() += bucket.view_count;
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! Round trip through an S3-compatible bucket. Runs against the MinIO of docker-compose (or the
+//! bucket in `NEWS_API__MEDIA__S3__*`) with `cargo test -p news-api --test s3_storage -- --ignored`.
+
+use news_api::media_storage::MediaStorage;
+use news_api::s3_storage::S3Storage;
+use news_api::settings::S3Settings;
+use uuid::Uuid;
+
+fn env_or(name: &str, default: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| default.to_string())
+}
+
+fn s3_settings() -> S3Settings {
+    S3Settings {
+        endpoint: env_or(
+            "NEWS_API__MEDIA__S3__ENDPOINT",
+            "http://localhost:9000",
+        ),
+        region: env_or("NEWS_API__MEDIA__S3__REGION", "us-east-1"),
+        bucket: env_or("NEWS_API__MEDIA__S3__BUCKET", "news-media"),
+        access_key: env_or("NEWS_API__MEDIA__S3__ACCESS_KEY", "minio-user"),
+        secret_key: env_or("NEWS_API__MEDIA__S3__SECRET_KEY", "minio-pass"),
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs a running MinIO, see docker-compose.yml"]
+async fn puts_gets_and_deletes_objects() {
+    let storage = S3Storage::new(&s3_settings()).unwrap();
+    let key = format!("tests/{}", Uuid::now_v7());
+
+    storage
+        .put(&key, "image/png", b"not really a png")
+        .await
+        .unwrap();
+    assert_eq!(
+        storage.get(&key).await.unwrap(),
+        b"not really a png"
+    );
+
+    storage.delete(&key).await.unwrap();
+    assert!(storage.get(&key).await.is_err());
+}